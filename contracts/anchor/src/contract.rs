@@ -9,9 +9,9 @@ use cosmwasm_std::{
 use cw2::set_contract_version;
 
 use crate::state::{
-    read_curr_neighbor_root_index, read_edge, read_neighbor_roots, read_root,
-    save_curr_neighbor_root_index, save_edge, save_neighbor_roots, save_root, save_subtree, Anchor,
-    LinkableMerkleTree, MerkleTree, ANCHOR, HASHER, NULLIFIERS, VERIFIER,
+    read_curr_neighbor_root_index, read_edge, read_leaf, read_leaf_index, read_neighbor_roots,
+    read_root, save_curr_neighbor_root_index, save_edge, save_neighbor_roots, save_root,
+    save_subtree, Anchor, LinkableMerkleTree, MerkleTree, ANCHOR, HASHER, NULLIFIERS, VERIFIER,
 };
 use codec::Encode;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
@@ -20,11 +20,15 @@ use protocol_cosmwasm::anchor::{
 };
 use protocol_cosmwasm::anchor_verifier::AnchorVerifier;
 use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::events::{CommitmentEvent, NullifierEvent};
 use protocol_cosmwasm::keccak::Keccak256;
 use protocol_cosmwasm::poseidon::Poseidon;
 use protocol_cosmwasm::structs::{
-    Edge, EdgeInfoResponse, MerkleRootInfoResponse, MerkleTreeInfoResponse,
-    NeighborRootInfoResponse, COSMOS_CHAIN_TYPE, HISTORY_LENGTH,
+    Edge, EdgeInfoResponse, EdgeState, EdgesResponse, IsKnownNullifierResponse,
+    IsKnownRootResponse, LeafIndexResponse, LeavesResponse, MerklePathResponse,
+    MerkleRootInfoResponse, MerkleTreeInfoResponse, NeighborRootInfoResponse, RootHistoryResponse,
+    SpentNullifiersResponse, COSMOS_CHAIN_TYPE, DEFAULT_LEAVES_LIMIT, HISTORY_LENGTH,
+    MAX_LEAVES_LIMIT,
 };
 use protocol_cosmwasm::token_wrapper::{
     ConfigResponse as TokenWrapperConfigResp, Cw20HookMsg as TokenWrapperHookMsg,
@@ -32,7 +36,8 @@ use protocol_cosmwasm::token_wrapper::{
     QueryMsg as TokenWrapperQueryMsg,
 };
 use protocol_cosmwasm::utils::{
-    compute_chain_id, compute_chain_id_type, element_encoder, truncate_and_pad,
+    batch_deposit_size, compute_chain_id, compute_chain_id_type, element_encoder,
+    sent_native_amount, truncate_and_pad, validate_deposit_amount,
 };
 use protocol_cosmwasm::zeroes::zeroes;
 
@@ -69,6 +74,7 @@ pub fn instantiate(
         levels: msg.levels,
         current_root_index: 0,
         next_index: 0,
+        tree_id: 0,
     };
 
     // Initialize the linkable merkle tree
@@ -143,6 +149,15 @@ pub fn execute(
             amount,
             info.funds,
         ),
+        // Wrap the native token & deposit it with multiple commitments
+        ExecuteMsg::BatchWrapAndDeposit { commitments } => batch_wrap_and_deposit_native(
+            deps,
+            env.clone(),
+            info.sender.to_string(),
+            env.contract.address.to_string(),
+            commitments,
+            info.funds,
+        ),
 
         // Withdraws the deposit & unwraps to valid token for `sender`
         ExecuteMsg::WithdrawAndUnwrap(msg) => withdraw_and_unwrap(deps, env, info, msg),
@@ -180,6 +195,9 @@ pub fn receive_cw20(
         Ok(Cw20HookMsg::DepositCw20 { commitment }) => {
             deposit_cw20(deps, env, commitment, recv_token_addr, recv_token_amt)
         }
+        Ok(Cw20HookMsg::BatchDepositCw20 { commitments }) => {
+            batch_deposit_cw20(deps, env, commitments, recv_token_addr, recv_token_amt)
+        }
         Ok(Cw20HookMsg::WrapToken {}) => wrap_token(deps, sender, recv_token_addr, recv_token_amt),
         Ok(Cw20HookMsg::WrapAndDeposit { commitment, amount }) => wrap_and_deposit_cw20(
             deps,
@@ -191,6 +209,15 @@ pub fn receive_cw20(
             recv_token_addr,
             recv_token_amt,
         ),
+        Ok(Cw20HookMsg::BatchWrapAndDeposit { commitments }) => batch_wrap_and_deposit_cw20(
+            deps,
+            env.clone(),
+            sender,
+            env.contract.address.to_string(),
+            commitments,
+            recv_token_addr,
+            recv_token_amt,
+        ),
         Err(_) => Err(ContractError::InvalidCw20HookMsg {}),
     }
 }
@@ -211,25 +238,28 @@ fn deposit_cw20(
         return Err(ContractError::Unauthorized {});
     }
 
-    if recv_token_amt < anchor.deposit_size {
-        return Err(ContractError::InsufficientFunds {});
-    }
+    validate_deposit_amount(recv_token_amt, anchor.deposit_size)?;
 
     // Handle the "deposit" cw20 tokens
     if let Some(commitment) = commitment {
         // Handle the "commitment"
-        let inserted_index = validate_and_store_commitment(deps.branch(), commitment)?;
+        let (tree_id, inserted_index, new_root) =
+            validate_and_store_commitment(deps.branch(), commitment)?;
 
         // No need to handle any cw20 token transfer
         // since "TokenWrapper" tokens are already sent to this contract
-        Ok(
-            Response::new().add_event(Event::new("anchor-deposit").add_attributes(vec![
+        Ok(Response::new()
+            .add_event(Event::new("anchor-deposit").add_attributes(vec![
                 attr("action", "deposit_cw20"),
                 attr("inserted_index", inserted_index.to_string()),
                 attr("commitment", format!("{:?}", commitment)),
                 attr("timestamp", env.block.time.seconds().to_string()),
-            ])),
-        )
+            ]))
+            .add_event(
+                CommitmentEvent::new("anchor", commitment, inserted_index, new_root)
+                    .tree_id(tree_id)
+                    .build(),
+            ))
     } else {
         Err(ContractError::Std(StdError::NotFound {
             kind: "Commitment".to_string(),
@@ -237,6 +267,38 @@ fn deposit_cw20(
     }
 }
 
+/// Deposit CW20 token("TokenWrapper") with multiple commitments
+fn batch_deposit_cw20(
+    deps: DepsMut,
+    env: Env,
+    commitments: Vec<[u8; 32]>,
+    recv_token_addr: String,
+    recv_token_amt: Uint128,
+) -> Result<Response, ContractError> {
+    let anchor: Anchor = ANCHOR.load(deps.storage)?;
+
+    // Validations
+    let cw20_addr = deps.api.addr_validate(recv_token_addr.as_str())?;
+    if anchor.tokenwrapper_addr != cw20_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_deposit_amount(
+        recv_token_amt,
+        batch_deposit_size(anchor.deposit_size, &commitments)?,
+    )?;
+
+    // Handle the "commitments"
+    let commitment_events = validate_and_store_commitments(deps, &commitments)?;
+
+    Ok(Response::new()
+        .add_event(Event::new("anchor-deposit").add_attributes(vec![
+            attr("action", "batch_deposit_cw20"),
+            attr("count", commitments.len().to_string()),
+            attr("timestamp", env.block.time.seconds().to_string()),
+        ]))
+        .add_events(commitment_events))
+}
+
 /// Wrap the cw20 token into "TokenWrapper" token
 fn wrap_token(
     deps: DepsMut,
@@ -290,6 +352,7 @@ fn unwrap_into_token(
             sender: Some(sender.clone()),
             recipient: Some(sender),
             token: Some(deps.api.addr_validate(token_addr.as_str())?),
+            denom: None,
             amount,
         })?,
     })];
@@ -433,7 +496,8 @@ pub fn withdraw(
             attr("fee", msg.fee),
             attr("commitment", format!("{:?}", msg.commitment)),
             attr("nullifier_hash", format!("{:?}", msg.nullifier_hash)),
-        ])))
+        ]))
+        .add_event(NullifierEvent::new("anchor", msg.nullifier_hash).build()))
 }
 
 /// Wrap the native token into "TokenWrapper" token
@@ -492,6 +556,7 @@ fn unwrap_native(
             sender: Some(sender.clone()),
             recipient: Some(sender),
             token: None,
+            denom: None,
             amount,
         })?,
     })];
@@ -516,6 +581,7 @@ fn wrap_and_deposit_native(
     let tokenwrapper = anchor.tokenwrapper_addr.as_str();
 
     // Validations
+    validate_deposit_amount(amount, anchor.deposit_size)?;
     let wrapper_config: TokenWrapperConfigResp = deps.querier.query_wasm_smart(
         anchor.tokenwrapper_addr.to_string(),
         &TokenWrapperQueryMsg::Config {},
@@ -526,21 +592,19 @@ fn wrap_and_deposit_native(
         tokenwrapper.to_string(),
         &TokenWrapperQueryMsg::GetAmountToWrap {
             target_amount: amount.to_string(),
+            token: None,
         },
     )?;
     let amt_to_wrap = Uint128::from_str(&amt_to_wrap_query.amount_to_wrap)?;
-
-    let is_sent_enough_token = sent_funds
-        .iter()
-        .any(|c| c.denom == token_denom.clone() && c.amount == amt_to_wrap);
-    if !is_sent_enough_token {
+    if sent_native_amount(&sent_funds, &token_denom)? != amt_to_wrap {
         return Err(ContractError::InsufficientFunds {});
     }
 
     // Handle the "deposit"
     if let Some(commitment) = commitment {
         // Handle the "commitment"
-        let inserted_index = validate_and_store_commitment(deps.branch(), commitment)?;
+        let (tree_id, inserted_index, new_root) =
+            validate_and_store_commitment(deps.branch(), commitment)?;
 
         // Wrap into the token and send directly to this contract
         let msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -552,14 +616,19 @@ fn wrap_and_deposit_native(
             funds: sent_funds,
         })];
 
-        Ok(Response::new().add_messages(msgs).add_event(
-            Event::new("anchor-deposit").add_attributes(vec![
+        Ok(Response::new()
+            .add_messages(msgs)
+            .add_event(Event::new("anchor-deposit").add_attributes(vec![
                 attr("action", "wrap_and_deposit_native"),
                 attr("inserted_index", inserted_index.to_string()),
                 attr("commitment", format!("{:?}", commitment)),
                 attr("timestamp", env.block.time.seconds().to_string()),
-            ]),
-        ))
+            ]))
+            .add_event(
+                CommitmentEvent::new("anchor", commitment, inserted_index, new_root)
+                    .tree_id(tree_id)
+                    .build(),
+            ))
     } else {
         Err(ContractError::Std(StdError::NotFound {
             kind: "Commitment".to_string(),
@@ -586,12 +655,14 @@ fn wrap_and_deposit_cw20(
     if anchor.tokenwrapper_addr == deps.api.addr_validate(recv_token_addr.as_str())? {
         return Err(ContractError::Unauthorized {});
     }
+    validate_deposit_amount(amount, anchor.deposit_size)?;
 
     // Check if the "recv_token_amt" == "ext_amt" + "wrapping_fee"
     let amt_to_wrap_query: GetAmountToWrapResponse = deps.querier.query_wasm_smart(
         tokenwrapper.to_string(),
         &TokenWrapperQueryMsg::GetAmountToWrap {
             target_amount: amount.to_string(),
+            token: Some(recv_token_addr.clone()),
         },
     )?;
     let amt_to_wrap = Uint128::from_str(&amt_to_wrap_query.amount_to_wrap)?;
@@ -603,7 +674,8 @@ fn wrap_and_deposit_cw20(
     // Handle the "deposit"
     if let Some(commitment) = commitment {
         // Handle the "commitment"
-        let inserted_index = validate_and_store_commitment(deps.branch(), commitment)?;
+        let (tree_id, inserted_index, new_root) =
+            validate_and_store_commitment(deps.branch(), commitment)?;
 
         // Wrap into the token and send directly to this contract
         let msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -619,14 +691,19 @@ fn wrap_and_deposit_cw20(
             funds: vec![],
         })];
 
-        Ok(Response::new().add_messages(msgs).add_event(
-            Event::new("anchor-deposit").add_attributes(vec![
+        Ok(Response::new()
+            .add_messages(msgs)
+            .add_event(Event::new("anchor-deposit").add_attributes(vec![
                 attr("action", "wrap_and_deposit_cw20"),
                 attr("inserted_index", inserted_index.to_string()),
                 attr("commitment", format!("{:?}", commitment)),
                 attr("timestamp", env.block.time.seconds().to_string()),
-            ]),
-        ))
+            ]))
+            .add_event(
+                CommitmentEvent::new("anchor", commitment, inserted_index, new_root)
+                    .tree_id(tree_id)
+                    .build(),
+            ))
     } else {
         Err(ContractError::Std(StdError::NotFound {
             kind: "Commitment".to_string(),
@@ -634,6 +711,121 @@ fn wrap_and_deposit_cw20(
     }
 }
 
+/// Wrap the native token & deposit it into the contract with multiple commitments
+fn batch_wrap_and_deposit_native(
+    deps: DepsMut,
+    env: Env,
+    sender: String,
+    recipient: String,
+    commitments: Vec<[u8; 32]>,
+    sent_funds: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    let tokenwrapper = anchor.tokenwrapper_addr.as_str();
+
+    // Validations
+    let deposit_amt = batch_deposit_size(anchor.deposit_size, &commitments)?;
+    let wrapper_config: TokenWrapperConfigResp = deps.querier.query_wasm_smart(
+        anchor.tokenwrapper_addr.to_string(),
+        &TokenWrapperQueryMsg::Config {},
+    )?;
+    let token_denom = wrapper_config.native_token_denom;
+
+    let amt_to_wrap_query: GetAmountToWrapResponse = deps.querier.query_wasm_smart(
+        tokenwrapper.to_string(),
+        &TokenWrapperQueryMsg::GetAmountToWrap {
+            target_amount: deposit_amt.to_string(),
+            token: None,
+        },
+    )?;
+    let amt_to_wrap = Uint128::from_str(&amt_to_wrap_query.amount_to_wrap)?;
+    if sent_native_amount(&sent_funds, &token_denom)? != amt_to_wrap {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    // Wrap into the token and send directly to this contract
+    let msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: tokenwrapper.to_string(),
+        msg: to_binary(&TokenWrapperExecuteMsg::Wrap {
+            sender: Some(sender),
+            recipient: Some(recipient),
+        })?,
+        funds: sent_funds,
+    })];
+
+    // Handle the "commitments"
+    let commitment_events = validate_and_store_commitments(deps, &commitments)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_event(Event::new("anchor-deposit").add_attributes(vec![
+            attr("action", "batch_wrap_and_deposit_native"),
+            attr("count", commitments.len().to_string()),
+            attr("timestamp", env.block.time.seconds().to_string()),
+        ]))
+        .add_events(commitment_events))
+}
+
+/// Wrap the cw20 token & deposit it into the contract with multiple commitments
+#[allow(clippy::too_many_arguments)]
+fn batch_wrap_and_deposit_cw20(
+    deps: DepsMut,
+    env: Env,
+    sender: String,
+    recipient: String,
+    commitments: Vec<[u8; 32]>,
+    recv_token_addr: String,
+    recv_token_amt: Uint128,
+) -> Result<Response, ContractError> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    let tokenwrapper = anchor.tokenwrapper_addr.as_str();
+
+    // Only non-"TokenWrapper" Cw20 token contract can execute this message.
+    if anchor.tokenwrapper_addr == deps.api.addr_validate(recv_token_addr.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let deposit_amt = batch_deposit_size(anchor.deposit_size, &commitments)?;
+
+    // Check if the "recv_token_amt" == "deposit_amt" + "wrapping_fee"
+    let amt_to_wrap_query: GetAmountToWrapResponse = deps.querier.query_wasm_smart(
+        tokenwrapper.to_string(),
+        &TokenWrapperQueryMsg::GetAmountToWrap {
+            target_amount: deposit_amt.to_string(),
+            token: Some(recv_token_addr.clone()),
+        },
+    )?;
+    let amt_to_wrap = Uint128::from_str(&amt_to_wrap_query.amount_to_wrap)?;
+    if recv_token_amt != amt_to_wrap {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    // Wrap into the token and send directly to this contract
+    let msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: recv_token_addr,
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: tokenwrapper.to_string(),
+            amount: amt_to_wrap,
+            msg: to_binary(&TokenWrapperHookMsg::Wrap {
+                sender: Some(sender),
+                recipient: Some(recipient),
+            })?,
+        })?,
+        funds: vec![],
+    })];
+
+    // Handle the "commitments"
+    let commitment_events = validate_and_store_commitments(deps, &commitments)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_event(Event::new("anchor-deposit").add_attributes(vec![
+            attr("action", "batch_wrap_and_deposit_cw20"),
+            attr("count", commitments.len().to_string()),
+            attr("timestamp", env.block.time.seconds().to_string()),
+        ]))
+        .add_events(commitment_events))
+}
+
 /// Withdraws the deposit & unwraps into valid token for `sender`
 fn withdraw_and_unwrap(
     deps: DepsMut,
@@ -737,6 +929,7 @@ fn withdraw_and_unwrap(
             msg: to_binary(&TokenWrapperExecuteMsg::Unwrap {
                 sender: None,
                 token: token_address,
+                denom: None,
                 amount: amt_to_recipient,
                 recipient: Some(recipient.clone()),
             })?,
@@ -772,7 +965,8 @@ fn withdraw_and_unwrap(
             attr("fee", msg.fee),
             attr("commitment", format!("{:?}", msg.commitment)),
             attr("nullifier_hash", format!("{:?}", msg.nullifier_hash)),
-        ])))
+        ]))
+        .add_event(NullifierEvent::new("anchor", msg.nullifier_hash).build()))
 }
 
 /// Sets a new handler for the contract
@@ -816,11 +1010,13 @@ fn add_edge(
     latest_leaf_index: u32,
     target: [u8; 32],
 ) -> Result<Response, ContractError> {
-    let linkable_tree = ANCHOR.load(deps.storage)?.linkable_tree;
+    let mut anchor = ANCHOR.load(deps.storage)?;
 
-    // Ensure anchor isn't at maximum edges
-    let curr_length = linkable_tree.get_latest_neighbor_edges(deps.storage).len();
-    if curr_length > linkable_tree.max_edges as usize {
+    // Ensure anchor isn't at maximum edges.
+    // NOTE: `max_edges` counts the anchor itself, since the `roots` of withdrawal
+    //       hold the local root followed by one root per edge.
+    let curr_length = anchor.linkable_tree.chain_id_list.len();
+    if curr_length + 1 >= anchor.linkable_tree.max_edges as usize {
         return Err(ContractError::TooManyEdges {});
     }
 
@@ -834,14 +1030,11 @@ fn add_edge(
     save_edge(deps.storage, src_chain_id, edge)?;
 
     // Update associated states
-    let curr_neighbor_root_idx = read_curr_neighbor_root_index(deps.storage, src_chain_id)?;
-    save_curr_neighbor_root_index(
-        deps.storage,
-        src_chain_id,
-        (curr_neighbor_root_idx + 1) % HISTORY_LENGTH,
-    )?;
+    save_curr_neighbor_root_index(deps.storage, src_chain_id, 0)?;
+    save_neighbor_roots(deps.storage, (src_chain_id, 0), root)?;
 
-    save_neighbor_roots(deps.storage, (src_chain_id, curr_neighbor_root_idx), root)?;
+    anchor.linkable_tree.chain_id_list.push(src_chain_id);
+    ANCHOR.save(deps.storage, &anchor)?;
 
     Ok(
         Response::new().add_event(Event::new("anchor-edge_add").add_attributes(vec![
@@ -892,11 +1085,36 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&get_config(deps)?),
         QueryMsg::EdgeInfo { id } => to_binary(&get_edge_info(deps, id)?),
+        QueryMsg::Edges {} => to_binary(&get_edges(deps)?),
         QueryMsg::NeighborRootInfo { chain_id, id } => {
             to_binary(&get_neighbor_root_info(deps, chain_id, id)?)
         }
         QueryMsg::MerkleTreeInfo {} => to_binary(&get_merkle_tree_info(deps)?),
         QueryMsg::MerkleRootInfo { id } => to_binary(&get_merkle_root(deps, id)?),
+        QueryMsg::Leaves {
+            tree_id,
+            start_index,
+            limit,
+        } => to_binary(&get_leaves(deps, tree_id, start_index, limit)?),
+        QueryMsg::LeafIndex { commitment } => to_binary(&get_leaf_index(deps, commitment)?),
+        QueryMsg::MerklePath {
+            tree_id,
+            leaf_index,
+        } => to_binary(&get_merkle_path(deps, tree_id, leaf_index)?),
+        QueryMsg::IsKnownNullifier { nullifier } => {
+            to_binary(&get_is_known_nullifier(deps, nullifier)?)
+        }
+        QueryMsg::SpentNullifiers { nullifiers } => {
+            to_binary(&get_spent_nullifiers(deps, nullifiers)?)
+        }
+        QueryMsg::IsKnownRoot { root } => to_binary(&get_is_known_root(deps, root)?),
+        QueryMsg::RootHistory {} => to_binary(&get_root_history(deps)?),
+        QueryMsg::IsKnownNeighborRoot { chain_id, root } => {
+            to_binary(&get_is_known_neighbor_root(deps, chain_id, root)?)
+        }
+        QueryMsg::NeighborRootHistory { chain_id } => {
+            to_binary(&get_neighbor_root_history(deps, chain_id)?)
+        }
     }
 }
 
@@ -920,6 +1138,27 @@ pub fn get_edge_info(deps: Deps, id: u64) -> StdResult<EdgeInfoResponse> {
     })
 }
 
+pub fn get_edges(deps: Deps) -> StdResult<EdgesResponse> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    let edges = anchor
+        .linkable_tree
+        .get_latest_neighbor_edges(deps.storage)
+        .into_iter()
+        .map(|edge| EdgeState {
+            src_chain_id: edge.src_chain_id,
+            root: edge.root,
+            latest_leaf_index: edge.latest_leaf_index,
+            target: edge.target,
+            curr_neighbor_root_index: read_curr_neighbor_root_index(
+                deps.storage,
+                edge.src_chain_id,
+            )
+            .unwrap_or_default(),
+        })
+        .collect();
+    Ok(EdgesResponse { edges })
+}
+
 pub fn get_neighbor_root_info(
     deps: Deps,
     chain_id: u64,
@@ -935,6 +1174,7 @@ pub fn get_merkle_tree_info(deps: Deps) -> StdResult<MerkleTreeInfoResponse> {
         levels: anchor.merkle_tree.levels,
         curr_root_index: anchor.merkle_tree.current_root_index,
         next_index: anchor.merkle_tree.next_index,
+        tree_id: anchor.merkle_tree.tree_id,
     })
 }
 
@@ -943,6 +1183,113 @@ pub fn get_merkle_root(deps: Deps, id: u32) -> StdResult<MerkleRootInfoResponse>
     Ok(MerkleRootInfoResponse { root })
 }
 
+pub fn get_leaves(
+    deps: Deps,
+    tree_id: Option<u32>,
+    start_index: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<LeavesResponse> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    let tree_id = tree_id.unwrap_or(anchor.merkle_tree.tree_id);
+    let start_index = start_index.unwrap_or_default();
+    let limit = limit.unwrap_or(DEFAULT_LEAVES_LIMIT).min(MAX_LEAVES_LIMIT);
+    let end_index = start_index
+        .saturating_add(limit)
+        .min(anchor.merkle_tree.tree_size(tree_id));
+    let leaves = (start_index..end_index)
+        .map(|i| read_leaf(deps.storage, tree_id, i))
+        .collect::<StdResult<Vec<[u8; 32]>>>()?;
+    Ok(LeavesResponse {
+        tree_id,
+        start_index,
+        leaves,
+    })
+}
+
+pub fn get_leaf_index(deps: Deps, commitment: [u8; 32]) -> StdResult<LeafIndexResponse> {
+    let (tree_id, leaf_index) = read_leaf_index(deps.storage, commitment)?;
+    Ok(LeafIndexResponse {
+        tree_id,
+        leaf_index,
+    })
+}
+
+pub fn get_merkle_path(
+    deps: Deps,
+    tree_id: Option<u32>,
+    leaf_index: u32,
+) -> StdResult<MerklePathResponse> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    let tree_id = tree_id.unwrap_or(anchor.merkle_tree.tree_id);
+    let (siblings, path_bits, root) = anchor
+        .merkle_tree
+        .merkle_path(tree_id, leaf_index, deps.storage)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Ok(MerklePathResponse {
+        tree_id,
+        leaf_index,
+        root,
+        siblings,
+        path_bits,
+    })
+}
+
+pub fn get_is_known_nullifier(
+    deps: Deps,
+    nullifier: [u8; 32],
+) -> StdResult<IsKnownNullifierResponse> {
+    Ok(IsKnownNullifierResponse {
+        known: is_known_nullifier(deps.storage, nullifier),
+    })
+}
+
+pub fn get_spent_nullifiers(
+    deps: Deps,
+    nullifiers: Vec<[u8; 32]>,
+) -> StdResult<SpentNullifiersResponse> {
+    let spent = nullifiers
+        .into_iter()
+        .map(|nullifier| is_known_nullifier(deps.storage, nullifier))
+        .collect();
+    Ok(SpentNullifiersResponse { spent })
+}
+
+pub fn get_is_known_root(deps: Deps, root: [u8; 32]) -> StdResult<IsKnownRootResponse> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    Ok(IsKnownRootResponse {
+        known: anchor.merkle_tree.is_known_root(root, deps.storage),
+    })
+}
+
+pub fn get_root_history(deps: Deps) -> StdResult<RootHistoryResponse> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    Ok(RootHistoryResponse {
+        roots: anchor.merkle_tree.root_history(deps.storage),
+    })
+}
+
+pub fn get_is_known_neighbor_root(
+    deps: Deps,
+    chain_id: u64,
+    root: [u8; 32],
+) -> StdResult<IsKnownRootResponse> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    Ok(IsKnownRootResponse {
+        known: anchor
+            .linkable_tree
+            .is_known_neighbor_root(chain_id, root, deps.storage),
+    })
+}
+
+pub fn get_neighbor_root_history(deps: Deps, chain_id: u64) -> StdResult<RootHistoryResponse> {
+    let anchor = ANCHOR.load(deps.storage)?;
+    Ok(RootHistoryResponse {
+        roots: anchor
+            .linkable_tree
+            .neighbor_root_history(chain_id, deps.storage),
+    })
+}
+
 // Check if the "nullifier" is already used or not.
 pub fn is_known_nullifier(store: &dyn Storage, nullifier: [u8; 32]) -> bool {
     NULLIFIERS.has(store, nullifier.to_vec())
@@ -959,16 +1306,18 @@ pub fn verify(
         .map_err(|_| ContractError::VerifyError)
 }
 
+/// Inserts the "commitment" into merkle tree.
+/// Returns the tree id, inserted leaf index and the new merkle root.
 pub fn validate_and_store_commitment(
     deps: DepsMut,
     commitment: [u8; 32],
-) -> Result<u32, ContractError> {
+) -> Result<(u32, u32, [u8; 32]), ContractError> {
     let anchor = ANCHOR.load(deps.storage)?;
     let mut merkle_tree = anchor.merkle_tree;
     let poseidon = HASHER.load(deps.storage)?;
-    let res = merkle_tree
-        .insert(poseidon, commitment, deps.storage)
-        .map_err(|_| ContractError::MerkleTreeIsFull)?;
+    let res = merkle_tree.insert(poseidon, commitment, deps.storage)?;
+    let new_root = read_root(deps.storage, merkle_tree.current_root_index)?;
+    let tree_id = merkle_tree.tree_id;
 
     ANCHOR.save(
         deps.storage,
@@ -982,10 +1331,38 @@ pub fn validate_and_store_commitment(
         },
     )?;
 
-    Ok(res)
+    Ok((tree_id, res, new_root))
+}
+
+/// Inserts the "commitments" into merkle tree, loading the hasher & saving the anchor once.
+/// Returns the commitment event of each leaf.
+pub fn validate_and_store_commitments(
+    deps: DepsMut,
+    commitments: &[[u8; 32]],
+) -> Result<Vec<Event>, ContractError> {
+    let mut anchor = ANCHOR.load(deps.storage)?;
+    let poseidon = HASHER.load(deps.storage)?;
+    let mut events: Vec<Event> = vec![];
+    for commitment in commitments {
+        let inserted_index =
+            anchor
+                .merkle_tree
+                .insert(poseidon.clone(), *commitment, deps.storage)?;
+        let new_root = read_root(deps.storage, anchor.merkle_tree.current_root_index)?;
+        events.push(
+            CommitmentEvent::new("anchor", *commitment, inserted_index, new_root)
+                .tree_id(anchor.merkle_tree.tree_id)
+                .build(),
+        );
+    }
+    ANCHOR.save(deps.storage, &anchor)?;
+    Ok(events)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // The pools deployed before the merkle node tracking have no nodes of the inserted leaves.
+    let anchor = ANCHOR.load(deps.storage)?;
+    anchor.merkle_tree.start_node_tracking(deps.storage)?;
     Ok(Response::default())
 }
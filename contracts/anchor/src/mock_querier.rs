@@ -18,7 +18,10 @@ use protocol_cosmwasm::token_wrapper::{
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Config {},
-    GetAmountToWrap { target_amount: String },
+    GetAmountToWrap {
+        target_amount: String,
+        token: Option<String>,
+    },
 }
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
@@ -71,20 +74,23 @@ impl WasmMockQuerier {
                 QueryMsg::Config {} => SystemResult::Ok(ContractResult::Ok(
                     to_binary(&TokenWrapperConfigResponse {
                         governor: "governor".to_string(),
+                        handler: "handler".to_string(),
                         native_token_denom: "uusd".to_string(),
                         fee_recipient: "fee-recipient".to_string(),
                         fee_percentage: "0.01".to_string(),
                         is_native_allowed: "true".to_string(),
                         wrapping_limit: "100".to_string(),
                         proposal_nonce: "0".to_string(),
+                        unwrap_fee_percentage: "0".to_string(),
+                        is_unwrap_fee_retained: "false".to_string(),
                     })
                     .unwrap(),
                 )),
-                QueryMsg::GetAmountToWrap { target_amount } => {
+                QueryMsg::GetAmountToWrap { target_amount, .. } => {
                     SystemResult::Ok(ContractResult::Ok(
                         to_binary(&GetAmountToWrapResponse {
+                            amount_to_wrap: target_amount.clone(),
                             target_amount,
-                            amount_to_wrap: "1000000".to_string(),
                         })
                         .unwrap(),
                     ))
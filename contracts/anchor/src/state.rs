@@ -1,12 +1,13 @@
-use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use protocol_cosmwasm::anchor_verifier::AnchorVerifier;
 use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::merkle_tree::{self, save_node};
 use protocol_cosmwasm::poseidon::Poseidon;
-use protocol_cosmwasm::structs::{ChainId, Edge, ROOT_HISTORY_SIZE};
+use protocol_cosmwasm::structs::{ChainId, Edge, HISTORY_LENGTH, ROOT_HISTORY_SIZE};
 use protocol_cosmwasm::zeroes;
 
 pub const ANCHOR: Item<Anchor> = Item::new("anchor");
@@ -18,6 +19,7 @@ pub const CURR_NEIGHBOR_ROOT_INDEX: Map<String, u32> = Map::new("curr_neighbor_r
 pub const NEIGHBOR_ROOTS: Map<(String, String), [u8; 32]> = Map::new("neighbor_roots");
 pub const MERKLEROOTS: Map<String, [u8; 32]> = Map::new("merkle_roots");
 pub const FILLED_SUBTREES: Map<String, [u8; 32]> = Map::new("filled_subtrees");
+pub const LEAF_INDICES: Map<Vec<u8>, (u32, u32)> = Map::new("leaf_indices");
 pub const NULLIFIERS: Map<Vec<u8>, bool> = Map::new("used_nullifers");
 
 /// "Anchor"
@@ -110,7 +112,7 @@ impl LinkableMerkleTree {
 
             let curr_neighbor_root_index =
                 read_curr_neighbor_root_index(store, edge.src_chain_id).unwrap_or_default();
-            let neighbor_root_index = curr_neighbor_root_index + 1 % ROOT_HISTORY_SIZE;
+            let neighbor_root_index = (curr_neighbor_root_index + 1) % HISTORY_LENGTH;
 
             save_curr_neighbor_root_index(store, edge.src_chain_id, neighbor_root_index)?;
             save_neighbor_roots(store, (edge.src_chain_id, neighbor_root_index), edge.root)?;
@@ -153,6 +155,24 @@ impl LinkableMerkleTree {
             .collect()
     }
 
+    /// Returns the known roots of the neighbor chain, from the latest to the oldest.
+    pub fn neighbor_root_history(&self, chain_id: ChainId, store: &dyn Storage) -> Vec<[u8; 32]> {
+        let mut roots: Vec<[u8; 32]> = vec![];
+        let mut i = read_curr_neighbor_root_index(store, chain_id).unwrap_or_default();
+        for _ in 0..HISTORY_LENGTH {
+            if let Ok(r) = read_neighbor_roots(store, (chain_id, i)) {
+                roots.push(r);
+            }
+
+            if i == 0 {
+                i = HISTORY_LENGTH - 1;
+            } else {
+                i -= 1;
+            }
+        }
+        roots
+    }
+
     pub fn is_known_neighbor_root(
         &self,
         chain_id: ChainId,
@@ -163,22 +183,7 @@ impl LinkableMerkleTree {
             return false;
         }
 
-        let mut i = read_curr_neighbor_root_index(store, chain_id).unwrap_or_default();
-        for _ in 0..ROOT_HISTORY_SIZE {
-            if let Ok(r) = read_neighbor_roots(store, (chain_id, i)) {
-                if r == root {
-                    return true;
-                }
-
-                if i == 0 {
-                    i = ROOT_HISTORY_SIZE - 1;
-                } else {
-                    i -= 1;
-                }
-            }
-        }
-
-        false
+        self.neighbor_root_history(chain_id, store).contains(&root)
     }
 
     pub fn is_valid_neighbor_roots(&self, roots: &[[u8; 32]], store: &dyn Storage) -> bool {
@@ -206,6 +211,9 @@ pub struct MerkleTree {
     pub levels: u32,
     pub current_root_index: u32,
     pub next_index: u32,
+    /// Id of the current tree, increased when the tree is full
+    #[serde(default)]
+    pub tree_id: u32,
 }
 
 pub fn save_subtree(store: &mut dyn Storage, k: u32, data: &[u8; 32]) -> StdResult<()> {
@@ -224,6 +232,32 @@ pub fn read_root(store: &dyn Storage, k: u32) -> StdResult<[u8; 32]> {
     MERKLEROOTS.load(store, k.to_string())
 }
 
+/// Saves the (tree_id, leaf_index) of the leaf, rejecting the commitment already inserted into any tree,
+/// so that each commitment is mapped to a single (tree_id, leaf_index).
+/// The leaf itself is saved as the level 0 node of the tree.
+pub fn save_leaf_index(
+    store: &mut dyn Storage,
+    tree_id: u32,
+    k: u32,
+    data: &[u8; 32],
+) -> Result<(), ContractError> {
+    if LEAF_INDICES.has(store, data.to_vec()) {
+        return Err(ContractError::DuplicateCommitment);
+    }
+    LEAF_INDICES.save(store, data.to_vec(), &(tree_id, k))?;
+    Ok(())
+}
+
+/// Returns the leaf, which is the level 0 node of the tree
+pub fn read_leaf(store: &dyn Storage, tree_id: u32, k: u32) -> StdResult<[u8; 32]> {
+    merkle_tree::read_node(store, tree_id, 0, k)?.ok_or_else(|| StdError::not_found("leaf"))
+}
+
+/// Returns the (tree_id, leaf_index) of the `commitment`
+pub fn read_leaf_index(store: &dyn Storage, commitment: [u8; 32]) -> StdResult<(u32, u32)> {
+    LEAF_INDICES.load(store, commitment.to_vec())
+}
+
 impl MerkleTree {
     fn hash_left_right(
         &self,
@@ -241,11 +275,16 @@ impl MerkleTree {
         leaf: [u8; 32],
         store: &mut dyn Storage,
     ) -> Result<u32, ContractError> {
+        // Open a new tree if the current one is full.
+        // The roots of the previous tree are kept in the root history,
+        // so the proofs against them are still valid until they get rotated out.
+        if self.next_index == 2u32.pow(self.levels as u32) {
+            self.tree_id += 1;
+            self.next_index = 0;
+        }
         let next_index = self.next_index;
-        assert!(
-            next_index != 2u32.pow(self.levels as u32),
-            "Merkle tree is full"
-        );
+
+        save_leaf_index(store, self.tree_id, next_index, &leaf)?;
 
         let mut current_index = next_index;
         let mut current_level_hash = leaf;
@@ -253,6 +292,7 @@ impl MerkleTree {
         let mut right: [u8; 32];
 
         for i in 0..self.levels {
+            save_node(store, self.tree_id, i, current_index, &current_level_hash)?;
             if current_index % 2 == 0 {
                 left = current_level_hash;
                 right = zeroes::zeroes(i);
@@ -265,6 +305,7 @@ impl MerkleTree {
             current_level_hash = self.hash_left_right(hasher.clone(), left, right)?;
             current_index /= 2;
         }
+        save_node(store, self.tree_id, self.levels, 0, &current_level_hash)?;
 
         let new_root_index = (self.current_root_index + 1) % ROOT_HISTORY_SIZE;
         self.current_root_index = new_root_index;
@@ -294,4 +335,60 @@ impl MerkleTree {
 
         false
     }
+
+    /// Returns the known roots of the tree, from the latest to the oldest.
+    pub fn root_history(&self, store: &dyn Storage) -> Vec<[u8; 32]> {
+        let mut roots: Vec<[u8; 32]> = vec![];
+        let mut i = self.current_root_index;
+        for _ in 0..ROOT_HISTORY_SIZE {
+            if let Ok(r) = read_root(store, i) {
+                roots.push(r);
+            }
+
+            if i == 0 {
+                i = ROOT_HISTORY_SIZE - 1;
+            } else {
+                i -= 1;
+            }
+        }
+        roots
+    }
+
+    /// Returns the number of leaves in the tree of `tree_id`.
+    pub fn tree_size(&self, tree_id: u32) -> u32 {
+        match tree_id {
+            id if id < self.tree_id => 2u32.pow(self.levels as u32),
+            id if id == self.tree_id => self.next_index,
+            _ => 0,
+        }
+    }
+
+    /// Returns the merkle path of the leaf at `leaf_index` in the tree of `tree_id`,
+    /// from the nodes stored on insertion.
+    /// Returns the sibling hashes, path bits(`true` if the node is the right child) & root.
+    pub fn merkle_path(
+        &self,
+        tree_id: u32,
+        leaf_index: u32,
+        store: &dyn Storage,
+    ) -> Result<(Vec<[u8; 32]>, Vec<bool>, [u8; 32]), ContractError> {
+        if leaf_index >= self.tree_size(tree_id) {
+            return Err(ContractError::ItemNotFound);
+        }
+        merkle_tree::merkle_path(store, tree_id, self.levels, leaf_index)
+    }
+
+    /// Starts the tracking of merkle nodes for the tree filled before it, on migration.
+    /// The merkle paths of the earlier leaves are not available afterwards.
+    pub fn start_node_tracking(&self, store: &mut dyn Storage) -> StdResult<()> {
+        let root = read_root(store, self.current_root_index)?;
+        merkle_tree::start_node_tracking(
+            store,
+            self.tree_id,
+            self.levels,
+            self.next_index,
+            root,
+            read_subtree,
+        )
+    }
 }
@@ -12,15 +12,22 @@ use cosmwasm_std::testing::MockApi;
 use cosmwasm_std::testing::MockStorage;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::OwnedDeps;
-use cosmwasm_std::{attr, coins, to_binary, CosmosMsg, Uint128, WasmMsg};
+use cosmwasm_std::{attr, coins, from_binary, to_binary, Coin, CosmosMsg, Uint128, WasmMsg};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
-use protocol_cosmwasm::anchor::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, WithdrawMsg};
+use protocol_cosmwasm::anchor::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, WithdrawMsg};
 use protocol_cosmwasm::error::ContractError;
-use protocol_cosmwasm::structs::COSMOS_CHAIN_TYPE;
+use protocol_cosmwasm::events::hex_encode;
+use protocol_cosmwasm::structs::{
+    EdgeState, EdgesResponse, IsKnownRootResponse, LeafIndexResponse, LeavesResponse,
+    MerklePathResponse, RootHistoryResponse, COSMOS_CHAIN_TYPE,
+};
+use protocol_cosmwasm::token_wrapper::{
+    Cw20HookMsg as TokenWrapperHookMsg, ExecuteMsg as TokenWrapperExecuteMsg,
+};
 use protocol_cosmwasm::utils::{compute_chain_id_type, truncate_and_pad};
 
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, query};
 #[cfg(test)]
 use crate::test_util::Element;
 
@@ -89,6 +96,20 @@ fn test_anchor_should_be_able_to_deposit() {
     let mut element: [u8; 32] = [0u8; 32];
     element.copy_from_slice(&res.into_repr().to_bytes_le());
 
+    // Should fail to "deposit" cw20 tokens if the amount is not exact.
+    let info = mock_info(TOKENWRAPPER_ADDR, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: DEPOSITOR.to_string(),
+        amount: Uint128::from(DEPOSIT_SIZE + 1),
+        msg: to_binary(&Cw20HookMsg::DepositCw20 {
+            commitment: Some(element),
+        })
+        .unwrap(),
+    });
+
+    let err = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidDepositAmount);
+
     // Should "deposit" cw20 tokens with success.
     let info = mock_info(TOKENWRAPPER_ADDR, &[]);
     let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
@@ -101,7 +122,275 @@ fn test_anchor_should_be_able_to_deposit() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
+
+    // The existing attributes are kept along with the structured event
+    assert_eq!(
+        response.events[0].attributes[..3],
+        [
+            attr("action", "deposit_cw20"),
+            attr("inserted_index", "0"),
+            attr("commitment", format!("{:?}", element)),
+        ]
+    );
+
+    let commitment_event = &response.events[1];
+    assert_eq!(commitment_event.ty, "anchor-commitment");
+    assert_eq!(
+        commitment_event.attributes[..3],
+        [
+            attr("tree_id", "0"),
+            attr("leaf_index", "0"),
+            attr("commitment", hex_encode(&element)),
+        ]
+    );
+
+    // Should be able to query the "leaves".
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: None,
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.leaves, vec![element]);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LeafIndex {
+            commitment: element,
+        },
+    )
+    .unwrap();
+    let leaf_index_response: LeafIndexResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaf_index_response.leaf_index, 0);
+}
+
+#[test]
+fn test_anchor_should_reject_duplicate_commitment() {
+    let mut deps = create_anchor();
+
+    let params = setup_params(Curve::Bn254, 5, 3);
+    let poseidon = Poseidon::new(params);
+    let res = poseidon.hash_two(&Fr::one(), &Fr::one()).unwrap();
+    let mut element: [u8; 32] = [0u8; 32];
+    element.copy_from_slice(&res.into_repr().to_bytes_le());
+
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: DEPOSITOR.to_string(),
+        amount: Uint128::from(DEPOSIT_SIZE),
+        msg: to_binary(&Cw20HookMsg::DepositCw20 {
+            commitment: Some(element),
+        })
+        .unwrap(),
+    });
+    let info = mock_info(TOKENWRAPPER_ADDR, &[]);
+    let _ = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg.clone()).unwrap();
+
+    // Should fail to deposit the same commitment again.
+    let info = mock_info(TOKENWRAPPER_ADDR, &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap_err();
+    assert_eq!(err, ContractError::DuplicateCommitment);
+
+    // Should fail to batch deposit the already inserted commitment.
+    let info = mock_info(TOKENWRAPPER_ADDR, &[]);
+    let batch_deposit_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: DEPOSITOR.to_string(),
+        amount: Uint128::from(DEPOSIT_SIZE * 2),
+        msg: to_binary(&Cw20HookMsg::BatchDepositCw20 {
+            commitments: vec![[1u8; 32], element],
+        })
+        .unwrap(),
+    });
+    let err = execute(deps.as_mut(), mock_env(), info, batch_deposit_msg).unwrap_err();
+    assert_eq!(err, ContractError::DuplicateCommitment);
+
+    // The leaf index of the commitment is not overwritten.
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LeafIndex {
+            commitment: element,
+        },
+    )
+    .unwrap();
+    let leaf_index_response: LeafIndexResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaf_index_response.tree_id, 0);
+    assert_eq!(leaf_index_response.leaf_index, 0);
+}
+
+#[test]
+fn test_anchor_should_be_able_to_batch_deposit() {
+    let mut deps = create_anchor();
+
+    let commitments: Vec<[u8; 32]> = vec![[1u8; 32], [2u8; 32], [3u8; 32]];
+
+    // Should fail to "batch deposit" if the amount is not exact.
+    let info = mock_info(TOKENWRAPPER_ADDR, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: DEPOSITOR.to_string(),
+        amount: Uint128::from(DEPOSIT_SIZE * 2),
+        msg: to_binary(&Cw20HookMsg::BatchDepositCw20 {
+            commitments: commitments.clone(),
+        })
+        .unwrap(),
+    });
+    let err = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds {});
+
+    // Should "batch deposit" cw20 tokens with success.
+    let info = mock_info(TOKENWRAPPER_ADDR, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: DEPOSITOR.to_string(),
+        amount: Uint128::from(DEPOSIT_SIZE * 3),
+        msg: to_binary(&Cw20HookMsg::BatchDepositCw20 {
+            commitments: commitments.clone(),
+        })
+        .unwrap(),
+    });
+    let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
+    assert_eq!(response.events.len(), 4);
+    for (i, commitment) in commitments.iter().enumerate() {
+        let commitment_event = &response.events[i + 1];
+        assert_eq!(commitment_event.ty, "anchor-commitment");
+        assert_eq!(
+            commitment_event.attributes[1..3],
+            [
+                attr("leaf_index", i.to_string()),
+                attr("commitment", hex_encode(commitment)),
+            ]
+        );
+    }
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: None,
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.leaves, commitments);
+}
+
+#[test]
+fn test_anchor_should_open_new_tree_when_full() {
+    let mut deps = crate::mock_querier::mock_dependencies(&[]);
+    let instantiate_msg = InstantiateMsg {
+        max_edges: MAX_EDGES,
+        levels: 1,
+        deposit_size: Uint128::from(DEPOSIT_SIZE),
+        tokenwrapper_addr: TOKENWRAPPER_ADDR.to_string(),
+        handler: HANDLER.to_string(),
+    };
+    let _ = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        instantiate_msg,
+    )
+    .unwrap();
+
+    // Should "batch deposit" 3 commitments across the trees of 2 leaves.
+    let commitments: Vec<[u8; 32]> = vec![[1u8; 32], [2u8; 32], [3u8; 32]];
+    let info = mock_info(TOKENWRAPPER_ADDR, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: DEPOSITOR.to_string(),
+        amount: Uint128::from(DEPOSIT_SIZE * 3),
+        msg: to_binary(&Cw20HookMsg::BatchDepositCw20 {
+            commitments: commitments.clone(),
+        })
+        .unwrap(),
+    });
+    let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
+    for i in 0..3 {
+        assert_eq!(
+            response.events[i + 1].attributes[..2],
+            [
+                attr("tree_id", (i / 2).to_string()),
+                attr("leaf_index", (i % 2).to_string()),
+            ]
+        );
+    }
+    // Root of the full(1st) tree
+    let full_tree_root = crate::state::read_root(&deps.storage, 2).unwrap();
+
+    // Should be able to query the leaves & merkle path of the full tree.
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LeafIndex {
+            commitment: commitments[2],
+        },
+    )
+    .unwrap();
+    let leaf_index_response: LeafIndexResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaf_index_response.tree_id, 1);
+    assert_eq!(leaf_index_response.leaf_index, 0);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: Some(0),
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.leaves, commitments[..2].to_vec());
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MerklePath {
+            tree_id: Some(0),
+            leaf_index: 0,
+        },
+    )
+    .unwrap();
+    let path_response: MerklePathResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(path_response.siblings, vec![commitments[1]]);
+    assert_eq!(path_response.root, full_tree_root);
+
+    // Should keep the roots of the full tree as known roots.
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::IsKnownRoot {
+            root: full_tree_root,
+        },
+    )
+    .unwrap();
+    let is_known_response: IsKnownRootResponse = from_binary(&query_bin).unwrap();
+    assert!(is_known_response.known);
+
+    // Should fill the new tree & open the 3rd tree with the next deposit.
+    for (commitment, tree_id, leaf_index) in [([4u8; 32], "1", "1"), ([5u8; 32], "2", "0")] {
+        let info = mock_info(TOKENWRAPPER_ADDR, &[]);
+        let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: DEPOSITOR.to_string(),
+            amount: Uint128::from(DEPOSIT_SIZE),
+            msg: to_binary(&Cw20HookMsg::DepositCw20 {
+                commitment: Some(commitment),
+            })
+            .unwrap(),
+        });
+        let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
+        assert_eq!(
+            response.events[1].attributes[..2],
+            [attr("tree_id", tree_id), attr("leaf_index", leaf_index)]
+        );
+    }
 }
 
 #[test]
@@ -143,7 +432,7 @@ fn test_anchor_fail_when_any_byte_is_changed_in_proof() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_elements[0].0;
@@ -221,7 +510,7 @@ fn test_anchor_fail_when_invalid_merkle_roots() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_elements[0].0;
@@ -295,7 +584,7 @@ fn test_anchor_works_with_wasm_utils() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_elements[0].0;
@@ -326,7 +615,7 @@ fn test_anchor_works_with_wasm_utils() {
         ExecuteMsg::Withdraw(withdraw_msg),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let expected_recipient = RECIPIENT.to_string();
     let expected_messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -380,7 +669,7 @@ fn test_anchor_works() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_elements[0].0;
@@ -410,7 +699,7 @@ fn test_anchor_works() {
         ExecuteMsg::Withdraw(withdraw_msg),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let expected_recipient = RECIPIENT.to_string();
     let expected_messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -464,7 +753,7 @@ fn test_anchor_fail_when_relayer_is_diff_from_that_in_proof_generation() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_elements[0].0;
@@ -537,7 +826,7 @@ fn test_anchor_fail_when_fee_submitted_is_changed() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_elements[0].0;
@@ -683,7 +972,7 @@ fn test_anchor_wrap_and_deposit_native() {
     let res = execute(deps.as_mut(), mock_env(), info, wrap_and_deposit_native_msg).unwrap();
 
     assert_eq!(res.messages.len(), 1);
-    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events.len(), 2);
 }
 
 #[test]
@@ -712,7 +1001,143 @@ fn test_anchor_wrap_and_deposit_cw20() {
     let res = execute(deps.as_mut(), mock_env(), info, wrap_deposit_cw20_msg).unwrap();
 
     assert_eq!(res.messages.len(), 1);
-    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events.len(), 2);
+}
+
+#[test]
+fn test_anchor_batch_wrap_and_deposit_native() {
+    let mut deps = create_anchor();
+    let commitments: Vec<[u8; 32]> = vec![[1u8; 32], [2u8; 32]];
+
+    // Should fail to "wrap & deposit" the amount other than the deposit size.
+    let info = mock_info(DEPOSITOR, &coins(DEPOSIT_SIZE + 1, "uusd"));
+    let wrap_and_deposit_native_msg = ExecuteMsg::WrapAndDeposit {
+        commitment: Some([3u8; 32]),
+        amount: Uint128::from(DEPOSIT_SIZE + 1),
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, wrap_and_deposit_native_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidDepositAmount);
+
+    // Should fail to "batch wrap & deposit" with the insufficient funds.
+    let info = mock_info(DEPOSITOR, &coins(DEPOSIT_SIZE, "uusd"));
+    let batch_msg = ExecuteMsg::BatchWrapAndDeposit {
+        commitments: commitments.clone(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, batch_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds {});
+
+    // Should fail to "batch wrap & deposit" with other tokens along.
+    let info = mock_info(
+        DEPOSITOR,
+        &[
+            Coin::new(DEPOSIT_SIZE * 2, "uusd"),
+            Coin::new(DEPOSIT_SIZE, "uluna"),
+        ],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, batch_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::UnnecessaryFunds {});
+
+    // Should "batch wrap & deposit" native token with success.
+    let info = mock_info(DEPOSITOR, &coins(DEPOSIT_SIZE * 2, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info, batch_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TOKENWRAPPER_ADDR.to_string(),
+            msg: to_binary(&TokenWrapperExecuteMsg::Wrap {
+                sender: Some(DEPOSITOR.to_string()),
+                recipient: Some(mock_env().contract.address.to_string()),
+            })
+            .unwrap(),
+            funds: coins(DEPOSIT_SIZE * 2, "uusd"),
+        })
+    );
+    assert_eq!(
+        res.events[0].attributes[..2],
+        [
+            attr("action", "batch_wrap_and_deposit_native"),
+            attr("count", "2"),
+        ]
+    );
+    assert_eq!(res.events.len(), 3);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: None,
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.leaves, commitments);
+}
+
+#[test]
+fn test_anchor_batch_wrap_and_deposit_cw20() {
+    let mut deps = create_anchor();
+    let commitments: Vec<[u8; 32]> = vec![[1u8; 32], [2u8; 32], [3u8; 32]];
+    let any_cw20_address = "any-cw20".to_string();
+
+    // Should fail to "batch wrap & deposit" the amount other than the total deposit size.
+    let info = mock_info(any_cw20_address.as_str(), &[]);
+    let batch_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: DEPOSITOR.to_string(),
+        amount: Uint128::from(DEPOSIT_SIZE * 2),
+        msg: to_binary(&Cw20HookMsg::BatchWrapAndDeposit {
+            commitments: commitments.clone(),
+        })
+        .unwrap(),
+    });
+    let err = execute(deps.as_mut(), mock_env(), info, batch_msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds {});
+
+    // Should "batch wrap & deposit" cw20 tokens with success.
+    let info = mock_info(any_cw20_address.as_str(), &[]);
+    let batch_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: DEPOSITOR.to_string(),
+        amount: Uint128::from(DEPOSIT_SIZE * 3),
+        msg: to_binary(&Cw20HookMsg::BatchWrapAndDeposit {
+            commitments: commitments.clone(),
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info, batch_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: any_cw20_address,
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: TOKENWRAPPER_ADDR.to_string(),
+                amount: Uint128::from(DEPOSIT_SIZE * 3),
+                msg: to_binary(&TokenWrapperHookMsg::Wrap {
+                    sender: Some(DEPOSITOR.to_string()),
+                    recipient: Some(mock_env().contract.address.to_string()),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.events[0].attributes[..2],
+        [
+            attr("action", "batch_wrap_and_deposit_cw20"),
+            attr("count", "3"),
+        ]
+    );
+    for (i, commitment) in commitments.iter().enumerate() {
+        assert_eq!(
+            res.events[i + 1].attributes[1..3],
+            [
+                attr("leaf_index", i.to_string()),
+                attr("commitment", hex_encode(commitment)),
+            ]
+        );
+    }
 }
 
 #[test]
@@ -750,7 +1175,7 @@ fn test_anchor_withdraw_and_unwrap_native() {
     };
     let res = execute(deps.as_mut(), mock_env(), info, wrap_and_deposit_native_msg).unwrap();
 
-    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_elements[0].0;
@@ -781,7 +1206,7 @@ fn test_anchor_withdraw_and_unwrap_native() {
         ExecuteMsg::WithdrawAndUnwrap(withdraw_msg),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 }
 
 #[test]
@@ -825,3 +1250,71 @@ fn test_anchor_set_handler() {
         ]
     );
 }
+
+#[test]
+fn test_anchor_add_and_update_edges() {
+    let src_chain_id: u64 = 1;
+    let root_1 = [1u8; 32];
+    let root_2 = [2u8; 32];
+    let target = [3u8; 32];
+
+    let mut deps = create_anchor();
+
+    // Add a new edge
+    let info = mock_info(HANDLER, &[]);
+    let update_edge_msg = ExecuteMsg::UpdateEdge {
+        src_chain_id,
+        root: root_1,
+        latest_leaf_index: 1,
+        target,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), update_edge_msg).unwrap();
+    assert_eq!(res.events[0].ty, "anchor-edge_add");
+
+    // Update the edge
+    let update_edge_msg = ExecuteMsg::UpdateEdge {
+        src_chain_id,
+        root: root_2,
+        latest_leaf_index: 2,
+        target,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), update_edge_msg).unwrap();
+    assert_eq!(res.events[0].ty, "anchor-edge_update");
+
+    // Query the edges
+    let edges: EdgesResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Edges {}).unwrap()).unwrap();
+    assert_eq!(
+        edges.edges,
+        vec![EdgeState {
+            src_chain_id,
+            root: root_2,
+            latest_leaf_index: 2,
+            target,
+            curr_neighbor_root_index: 1,
+        }]
+    );
+
+    let history: RootHistoryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::NeighborRootHistory {
+                chain_id: src_chain_id,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history.roots, vec![root_2, root_1]);
+
+    // Fails to add an edge when the anchor is at maximum edges
+    let update_edge_msg = ExecuteMsg::UpdateEdge {
+        src_chain_id: src_chain_id + 1,
+        root: root_1,
+        latest_leaf_index: 1,
+        target,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, update_edge_msg).unwrap_err();
+    assert_eq!(err, ContractError::TooManyEdges);
+}
@@ -7,22 +7,30 @@ use cosmwasm_std::{
 use cw2::set_contract_version;
 
 use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::events::{CommitmentEvent, NullifierEvent};
 use protocol_cosmwasm::keccak::Keccak256;
 use protocol_cosmwasm::mixer::{
-    ConfigResponse, Cw20HookMsg, DepositMsg, ExecuteMsg, InstantiateMsg, MerkleRootResponse,
-    MerkleTreeInfoResponse, QueryMsg, WithdrawMsg,
+    BatchDepositMsg, ConfigResponse, Cw20HookMsg, DepositMsg, ExecuteMsg, InstantiateMsg,
+    MerkleRootResponse, MerkleTreeInfoResponse, MigrateMsg, QueryMsg, WithdrawMsg,
 };
 use protocol_cosmwasm::mixer_verifier::MixerVerifier;
 use protocol_cosmwasm::poseidon::Poseidon;
-use protocol_cosmwasm::utils::truncate_and_pad;
+use protocol_cosmwasm::structs::{
+    IsKnownNullifierResponse, IsKnownRootResponse, LeafIndexResponse, LeavesResponse,
+    MerklePathResponse, RootHistoryResponse, SpentNullifiersResponse, DEFAULT_LEAVES_LIMIT,
+    MAX_LEAVES_LIMIT,
+};
+use protocol_cosmwasm::utils::{
+    batch_deposit_size, sent_native_amount, truncate_and_pad, validate_deposit_amount,
+};
 use protocol_cosmwasm::zeroes::zeroes;
 
 use codec::Encode;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
 use crate::state::{
-    read_root, save_root, save_subtree, MerkleTree, Mixer, MIXER, MIXERVERIFIER, POSEIDON,
-    USED_NULLIFIERS,
+    read_leaf, read_leaf_index, read_root, save_root, save_subtree, MerkleTree, Mixer, MIXER,
+    MIXERVERIFIER, POSEIDON, USED_NULLIFIERS,
 };
 
 // version info for migration info
@@ -48,6 +56,7 @@ pub fn instantiate(
         levels: msg.merkletree_levels,
         current_root_index: 0,
         next_index: 0,
+        tree_id: 0,
     };
     let native_token_denom = msg.native_token_denom;
     let cw20_address = match msg.cw20_address {
@@ -103,6 +112,8 @@ pub fn execute(
     match msg {
         // Deposit the "native" tokens with commitment
         ExecuteMsg::Deposit(msg) => deposit_native(deps, info, msg),
+        // Deposit the "native" tokens with multiple commitments
+        ExecuteMsg::BatchDeposit(msg) => batch_deposit_native(deps, info, msg),
         // Withdraw either "native" tokens or cw20 tokens.
         ExecuteMsg::Withdraw(msg) => withdraw(deps, info, msg),
         // Deposit the cw20 tokens with commitment
@@ -118,26 +129,17 @@ pub fn deposit_native(
     let mixer = MIXER.load(deps.storage)?;
 
     // Validations
-    if mixer.native_token_denom.is_none() {
-        return Err(ContractError::Std(StdError::GenericErr {
-            msg: "This mixer is for native tokens".to_string(),
-        }));
-    }
+    let sent_amount = sent_native_amount(&info.funds, native_token_denom(&mixer)?)?;
+    validate_deposit_amount(sent_amount, mixer.deposit_size)?;
     let native_token_denom = mixer.native_token_denom.unwrap();
-    let sent_tokens: Vec<Coin> = info
-        .funds
-        .into_iter()
-        .filter(|x| x.denom == native_token_denom)
-        .collect();
-    if sent_tokens.is_empty() || sent_tokens[0].amount < mixer.deposit_size {
-        return Err(ContractError::InsufficientFunds {});
-    }
 
     // Handle the "deposit"
     if let Some(commitment) = msg.commitment {
         let mut merkle_tree = mixer.merkle_tree;
         let poseidon = POSEIDON.load(deps.storage)?;
         let inserted_index = merkle_tree.insert(poseidon, commitment, deps.storage)?;
+        let new_root = read_root(deps.storage, merkle_tree.current_root_index)?;
+        let tree_id = merkle_tree.tree_id;
         MIXER.save(
             deps.storage,
             &Mixer {
@@ -147,13 +149,17 @@ pub fn deposit_native(
                 merkle_tree,
             },
         )?;
-        Ok(
-            Response::new().add_event(Event::new("mixer-deposit").add_attributes(vec![
+        Ok(Response::new()
+            .add_event(Event::new("mixer-deposit").add_attributes(vec![
                 attr("action", "deposit_native"),
                 attr("inserted_index", inserted_index.to_string()),
                 attr("commitment", format!("{:?}", commitment)),
-            ])),
-        )
+            ]))
+            .add_event(
+                CommitmentEvent::new("mixer", commitment, inserted_index, new_root)
+                    .tree_id(tree_id)
+                    .build(),
+            ))
     } else {
         Err(ContractError::Std(StdError::NotFound {
             kind: "Commitment".to_string(),
@@ -161,6 +167,64 @@ pub fn deposit_native(
     }
 }
 
+pub fn batch_deposit_native(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: BatchDepositMsg,
+) -> Result<Response, ContractError> {
+    let mixer = MIXER.load(deps.storage)?;
+
+    // Validations
+    let sent_amount = sent_native_amount(&info.funds, native_token_denom(&mixer)?)?;
+    validate_deposit_amount(
+        sent_amount,
+        batch_deposit_size(mixer.deposit_size, &msg.commitments)?,
+    )?;
+
+    // Handle the "deposit"
+    let commitment_events = insert_commitments(deps.storage, &msg.commitments)?;
+    Ok(Response::new()
+        .add_event(Event::new("mixer-deposit").add_attributes(vec![
+            attr("action", "batch_deposit_native"),
+            attr("count", msg.commitments.len().to_string()),
+        ]))
+        .add_events(commitment_events))
+}
+
+// Returns the native token denom of the mixer.
+fn native_token_denom(mixer: &Mixer) -> Result<&str, ContractError> {
+    match &mixer.native_token_denom {
+        Some(denom) => Ok(denom),
+        None => Err(ContractError::Std(StdError::GenericErr {
+            msg: "This mixer is for native tokens".to_string(),
+        })),
+    }
+}
+
+// Inserts the "commitments" into merkle tree, loading the hasher & saving the mixer once.
+// Returns the commitment event of each leaf.
+fn insert_commitments(
+    store: &mut dyn Storage,
+    commitments: &[[u8; 32]],
+) -> Result<Vec<Event>, ContractError> {
+    let mut mixer = MIXER.load(store)?;
+    let poseidon = POSEIDON.load(store)?;
+    let mut events: Vec<Event> = vec![];
+    for commitment in commitments {
+        let inserted_index = mixer
+            .merkle_tree
+            .insert(poseidon.clone(), *commitment, store)?;
+        let new_root = read_root(store, mixer.merkle_tree.current_root_index)?;
+        events.push(
+            CommitmentEvent::new("mixer", *commitment, inserted_index, new_root)
+                .tree_id(mixer.merkle_tree.tree_id)
+                .build(),
+        );
+    }
+    MIXER.save(store, &mixer)?;
+    Ok(events)
+}
+
 pub fn receive_cw20(
     deps: DepsMut,
     info: MessageInfo,
@@ -179,20 +243,17 @@ pub fn receive_cw20(
         return Err(ContractError::Unauthorized {});
     }
 
-    let sent_cw20_token_amt = cw20_msg.amount;
-    if sent_cw20_token_amt < mixer.deposit_size {
-        return Err(ContractError::InsufficientFunds {});
-    }
-
     match from_binary(&cw20_msg.msg) {
         Ok(Cw20HookMsg::DepositCw20 { commitment }) => {
+            validate_deposit_amount(cw20_msg.amount, mixer.deposit_size)?;
+
             // Handle the "deposit"
             if let Some(commitment) = commitment {
                 let mut merkle_tree = mixer.merkle_tree;
                 let poseidon = POSEIDON.load(deps.storage)?;
-                let inserted_index = merkle_tree
-                    .insert(poseidon, commitment, deps.storage)
-                    .map_err(|_| ContractError::MerkleTreeIsFull)?;
+                let inserted_index = merkle_tree.insert(poseidon, commitment, deps.storage)?;
+                let new_root = read_root(deps.storage, merkle_tree.current_root_index)?;
+                let tree_id = merkle_tree.tree_id;
 
                 MIXER.save(
                     deps.storage,
@@ -204,19 +265,38 @@ pub fn receive_cw20(
                     },
                 )?;
 
-                Ok(
-                    Response::new().add_event(Event::new("mixer-deposit").add_attributes(vec![
+                Ok(Response::new()
+                    .add_event(Event::new("mixer-deposit").add_attributes(vec![
                         attr("action", "deposit_cw20"),
                         attr("inserted_index", inserted_index.to_string()),
                         attr("commitment", format!("{:?}", commitment)),
-                    ])),
-                )
+                    ]))
+                    .add_event(
+                        CommitmentEvent::new("mixer", commitment, inserted_index, new_root)
+                            .tree_id(tree_id)
+                            .build(),
+                    ))
             } else {
                 Err(ContractError::Std(StdError::NotFound {
                     kind: "Commitment".to_string(),
                 }))
             }
         }
+        Ok(Cw20HookMsg::BatchDepositCw20 { commitments }) => {
+            validate_deposit_amount(
+                cw20_msg.amount,
+                batch_deposit_size(mixer.deposit_size, &commitments)?,
+            )?;
+
+            // Handle the "deposit"
+            let commitment_events = insert_commitments(deps.storage, &commitments)?;
+            Ok(Response::new()
+                .add_event(Event::new("mixer-deposit").add_attributes(vec![
+                    attr("action", "batch_deposit_cw20"),
+                    attr("count", commitments.len().to_string()),
+                ]))
+                .add_events(commitment_events))
+        }
         Err(_) => Err(ContractError::Std(StdError::generic_err(
             "invalid cw20 hook msg",
         ))),
@@ -366,7 +446,8 @@ pub fn withdraw(
             attr("recipient", recipient),
             attr("root", format!("{:?}", msg.root)),
             attr("nullifier_hash", format!("{:?}", msg.nullifier_hash)),
-        ])))
+        ]))
+        .add_event(NullifierEvent::new("mixer", msg.nullifier_hash).build()))
 }
 
 fn is_known_nullifier(store: &dyn Storage, nullifier: [u8; 32]) -> bool {
@@ -389,6 +470,24 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Config {} => to_binary(&get_config(deps)?),
         QueryMsg::MerkleTreeInfo {} => to_binary(&get_merkle_tree_info(deps)?),
         QueryMsg::MerkleRoot { id } => to_binary(&get_merkle_root(deps, id)?),
+        QueryMsg::Leaves {
+            tree_id,
+            start_index,
+            limit,
+        } => to_binary(&get_leaves(deps, tree_id, start_index, limit)?),
+        QueryMsg::LeafIndex { commitment } => to_binary(&get_leaf_index(deps, commitment)?),
+        QueryMsg::MerklePath {
+            tree_id,
+            leaf_index,
+        } => to_binary(&get_merkle_path(deps, tree_id, leaf_index)?),
+        QueryMsg::IsKnownNullifier { nullifier } => {
+            to_binary(&get_is_known_nullifier(deps, nullifier)?)
+        }
+        QueryMsg::SpentNullifiers { nullifiers } => {
+            to_binary(&get_spent_nullifiers(deps, nullifiers)?)
+        }
+        QueryMsg::IsKnownRoot { root } => to_binary(&get_is_known_root(deps, root)?),
+        QueryMsg::RootHistory {} => to_binary(&get_root_history(deps)?),
     }
 }
 
@@ -416,6 +515,7 @@ fn get_merkle_tree_info(deps: Deps) -> StdResult<MerkleTreeInfoResponse> {
         levels: mixer.merkle_tree.levels,
         current_root_index: mixer.merkle_tree.current_root_index,
         next_index: mixer.merkle_tree.next_index,
+        tree_id: mixer.merkle_tree.tree_id,
     })
 }
 
@@ -423,3 +523,93 @@ fn get_merkle_root(deps: Deps, id: u32) -> StdResult<MerkleRootResponse> {
     let root = read_root(deps.storage, id)?;
     Ok(MerkleRootResponse { root })
 }
+
+fn get_leaves(
+    deps: Deps,
+    tree_id: Option<u32>,
+    start_index: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<LeavesResponse> {
+    let mixer = MIXER.load(deps.storage)?;
+    let tree_id = tree_id.unwrap_or(mixer.merkle_tree.tree_id);
+    let start_index = start_index.unwrap_or_default();
+    let limit = limit.unwrap_or(DEFAULT_LEAVES_LIMIT).min(MAX_LEAVES_LIMIT);
+    let end_index = start_index
+        .saturating_add(limit)
+        .min(mixer.merkle_tree.tree_size(tree_id));
+    let leaves = (start_index..end_index)
+        .map(|i| read_leaf(deps.storage, tree_id, i))
+        .collect::<StdResult<Vec<[u8; 32]>>>()?;
+    Ok(LeavesResponse {
+        tree_id,
+        start_index,
+        leaves,
+    })
+}
+
+fn get_leaf_index(deps: Deps, commitment: [u8; 32]) -> StdResult<LeafIndexResponse> {
+    let (tree_id, leaf_index) = read_leaf_index(deps.storage, commitment)?;
+    Ok(LeafIndexResponse {
+        tree_id,
+        leaf_index,
+    })
+}
+
+fn get_merkle_path(
+    deps: Deps,
+    tree_id: Option<u32>,
+    leaf_index: u32,
+) -> StdResult<MerklePathResponse> {
+    let mixer = MIXER.load(deps.storage)?;
+    let tree_id = tree_id.unwrap_or(mixer.merkle_tree.tree_id);
+    let (siblings, path_bits, root) = mixer
+        .merkle_tree
+        .merkle_path(tree_id, leaf_index, deps.storage)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Ok(MerklePathResponse {
+        tree_id,
+        leaf_index,
+        root,
+        siblings,
+        path_bits,
+    })
+}
+
+fn get_is_known_nullifier(deps: Deps, nullifier: [u8; 32]) -> StdResult<IsKnownNullifierResponse> {
+    Ok(IsKnownNullifierResponse {
+        known: is_known_nullifier(deps.storage, nullifier),
+    })
+}
+
+fn get_spent_nullifiers(
+    deps: Deps,
+    nullifiers: Vec<[u8; 32]>,
+) -> StdResult<SpentNullifiersResponse> {
+    let spent = nullifiers
+        .into_iter()
+        .map(|nullifier| is_known_nullifier(deps.storage, nullifier))
+        .collect();
+    Ok(SpentNullifiersResponse { spent })
+}
+
+fn get_is_known_root(deps: Deps, root: [u8; 32]) -> StdResult<IsKnownRootResponse> {
+    let mixer = MIXER.load(deps.storage)?;
+    Ok(IsKnownRootResponse {
+        known: mixer.merkle_tree.is_known_root(root, deps.storage),
+    })
+}
+
+fn get_root_history(deps: Deps) -> StdResult<RootHistoryResponse> {
+    let mixer = MIXER.load(deps.storage)?;
+    Ok(RootHistoryResponse {
+        roots: mixer.merkle_tree.root_history(deps.storage),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // The pools deployed before the merkle node tracking have no nodes of the inserted leaves.
+    let mixer = MIXER.load(deps.storage)?;
+    mixer.merkle_tree.start_node_tracking(deps.storage)?;
+    Ok(Response::default())
+}
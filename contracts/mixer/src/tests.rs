@@ -12,12 +12,25 @@ use arkworks_setups::Curve;
 use cosmwasm_std::testing::{
     mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
-use cosmwasm_std::{attr, to_binary, Coin, CosmosMsg, OwnedDeps, Uint128, WasmMsg};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, Coin, CosmosMsg, Order, OwnedDeps, StdError, StdResult, Uint128,
+    WasmMsg,
+};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, migrate, query};
 use crate::test_util::Element;
-use protocol_cosmwasm::mixer::{Cw20HookMsg, DepositMsg, ExecuteMsg, InstantiateMsg, WithdrawMsg};
+use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::events::hex_encode;
+use protocol_cosmwasm::merkle_tree::MERKLE_NODES;
+use protocol_cosmwasm::mixer::{
+    BatchDepositMsg, Cw20HookMsg, DepositMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    WithdrawMsg,
+};
+use protocol_cosmwasm::structs::{
+    IsKnownNullifierResponse, IsKnownRootResponse, LeafIndexResponse, LeavesResponse,
+    MerklePathResponse, RootHistoryResponse, SpentNullifiersResponse,
+};
 use protocol_cosmwasm::utils::truncate_and_pad;
 
 const MERKLE_TREE_LEVELS: u32 = 30;
@@ -158,6 +171,45 @@ fn test_mixer_should_be_able_to_deposit_native_token() {
     .unwrap_err();
     assert_eq!(err.to_string(), "Insufficient_funds".to_string());
 
+    // Try the deposit with excess fund
+    let info = mock_info(
+        "depositor",
+        &[Coin::new(1_000_001_u128, NATIVE_TOKEN_DENOM)],
+    );
+    let deposit_msg = DepositMsg {
+        commitment: Some(element),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Deposit(deposit_msg),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Invalid deposit amount".to_string());
+
+    // Try the deposit with other coins along with the exact fund
+    let info = mock_info(
+        "depositor",
+        &[
+            Coin::new(1_000_000_u128, NATIVE_TOKEN_DENOM),
+            Coin::new(1_000_u128, "uluna"),
+        ],
+    );
+    let deposit_msg = DepositMsg {
+        commitment: Some(element),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Deposit(deposit_msg),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Unnecessary_funds".to_string());
+
     // Try the deposit with empty commitment
     let info = mock_info(
         "depositor",
@@ -190,7 +242,77 @@ fn test_mixer_should_be_able_to_deposit_native_token() {
         ExecuteMsg::Deposit(deposit_msg),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
+
+    // The existing attributes are kept along with the structured event
+    assert_eq!(
+        response.events[0].attributes,
+        vec![
+            attr("action", "deposit_native"),
+            attr("inserted_index", "0"),
+            attr("commitment", format!("{:?}", element)),
+        ]
+    );
+
+    let commitment_event = &response.events[1];
+    assert_eq!(commitment_event.ty, "mixer-commitment");
+    assert_eq!(
+        commitment_event.attributes[..3],
+        [
+            attr("tree_id", "0"),
+            attr("leaf_index", "0"),
+            attr("commitment", hex_encode(&element)),
+        ]
+    );
+}
+
+#[test]
+fn test_mixer_should_reject_duplicate_commitment() {
+    let mut deps = create_mixer(MixerType::Native);
+
+    let params = setup_params(Curve::Bn254, 5, 3);
+    let poseidon = Poseidon::new(params);
+    let res = poseidon.hash_two(&Fr::one(), &Fr::one()).unwrap();
+    let mut element: [u8; 32] = [0u8; 32];
+    element.copy_from_slice(&res.into_repr().to_bytes_le());
+
+    let info = mock_info(
+        "depositor",
+        &[Coin::new(1_000_000_u128, NATIVE_TOKEN_DENOM)],
+    );
+    let deposit_msg = DepositMsg {
+        commitment: Some(element),
+    };
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::Deposit(deposit_msg.clone()),
+    )
+    .unwrap();
+
+    // Try the deposit with the same commitment
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Deposit(deposit_msg),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Commitment already exists".to_string());
+
+    // The leaf index of the commitment is not overwritten
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LeafIndex {
+            commitment: element,
+        },
+    )
+    .unwrap();
+    let leaf_index_response: LeafIndexResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaf_index_response.tree_id, 0);
+    assert_eq!(leaf_index_response.leaf_index, 0);
 }
 
 #[test]
@@ -204,6 +326,34 @@ fn test_mixer_should_be_able_to_deposit_cw20_token() {
     let mut element: [u8; 32] = [0u8; 32];
     element.copy_from_slice(&res.into_repr().to_bytes_le());
 
+    // Try the deposit with insufficient fund
+    let info = mock_info(CW20_ADDRESS, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: CW20_ADDRESS.to_string(),
+        amount: Uint128::from(999_999_u128),
+        msg: to_binary(&Cw20HookMsg::DepositCw20 {
+            commitment: Some(element),
+        })
+        .unwrap(),
+    });
+
+    let err = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap_err();
+    assert_eq!(err.to_string(), "Insufficient_funds".to_string());
+
+    // Try the deposit with excess fund
+    let info = mock_info(CW20_ADDRESS, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: CW20_ADDRESS.to_string(),
+        amount: Uint128::from(1_000_001_u128),
+        msg: to_binary(&Cw20HookMsg::DepositCw20 {
+            commitment: Some(element),
+        })
+        .unwrap(),
+    });
+
+    let err = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid deposit amount".to_string());
+
     // Try the deposit for success
     let info = mock_info(CW20_ADDRESS, &[]);
     let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
@@ -216,7 +366,481 @@ fn test_mixer_should_be_able_to_deposit_cw20_token() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
+}
+
+#[test]
+fn test_mixer_should_be_able_to_batch_deposit() {
+    let mut deps = create_mixer(MixerType::Native);
+
+    let params = setup_params(Curve::Bn254, 5, 3);
+    let poseidon = Poseidon::new(params);
+    let commitments: Vec<[u8; 32]> = (0..3_u64)
+        .map(|i| {
+            let res = poseidon.hash_two(&Fr::from(i), &Fr::one()).unwrap();
+            let mut element: [u8; 32] = [0u8; 32];
+            element.copy_from_slice(&res.into_repr().to_bytes_le());
+            element
+        })
+        .collect();
+
+    // Try the batch deposit with empty commitments
+    let info = mock_info(
+        "depositor",
+        &[Coin::new(1_000_000_u128, NATIVE_TOKEN_DENOM)],
+    );
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BatchDeposit(BatchDepositMsg {
+            commitments: vec![],
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Commitment not found".to_string());
+
+    // Try the batch deposit with insufficient fund
+    let info = mock_info(
+        "depositor",
+        &[Coin::new(2_000_000_u128, NATIVE_TOKEN_DENOM)],
+    );
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BatchDeposit(BatchDepositMsg {
+            commitments: commitments.clone(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Insufficient_funds".to_string());
+
+    // Try the batch deposit for success
+    let info = mock_info(
+        "depositor",
+        &[Coin::new(3_000_000_u128, NATIVE_TOKEN_DENOM)],
+    );
+    let response = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BatchDeposit(BatchDepositMsg {
+            commitments: commitments.clone(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(response.events.len(), 4);
+    assert_eq!(
+        response.events[0].attributes,
+        vec![attr("action", "batch_deposit_native"), attr("count", "3")]
+    );
+    for (i, commitment) in commitments.iter().enumerate() {
+        let commitment_event = &response.events[i + 1];
+        assert_eq!(commitment_event.ty, "mixer-commitment");
+        assert_eq!(
+            commitment_event.attributes[1..3],
+            [
+                attr("leaf_index", i.to_string()),
+                attr("commitment", hex_encode(commitment)),
+            ]
+        );
+    }
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: None,
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.leaves, commitments);
+}
+
+#[test]
+fn test_mixer_should_be_able_to_batch_deposit_cw20_token() {
+    let mut deps = create_mixer(MixerType::Cw20);
+
+    let commitments: Vec<[u8; 32]> = vec![[1u8; 32], [2u8; 32]];
+
+    // Try the batch deposit with excess fund
+    let info = mock_info(CW20_ADDRESS, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: CW20_ADDRESS.to_string(),
+        amount: Uint128::from(3_000_000_u128),
+        msg: to_binary(&Cw20HookMsg::BatchDepositCw20 {
+            commitments: commitments.clone(),
+        })
+        .unwrap(),
+    });
+    let err = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid deposit amount".to_string());
+
+    // Try the batch deposit for success
+    let info = mock_info(CW20_ADDRESS, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: CW20_ADDRESS.to_string(),
+        amount: Uint128::from(2_000_000_u128),
+        msg: to_binary(&Cw20HookMsg::BatchDepositCw20 { commitments }).unwrap(),
+    });
+    let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
+    assert_eq!(response.events.len(), 3);
+    assert_eq!(
+        response.events[0].attributes,
+        vec![attr("action", "batch_deposit_cw20"), attr("count", "2")]
+    );
+}
+
+#[test]
+fn test_mixer_query_leaves_and_merkle_path() {
+    let mut deps = create_mixer(MixerType::Native);
+
+    // Deposit 3 commitments
+    let params = setup_params(Curve::Bn254, 5, 3);
+    let poseidon = Poseidon::new(params);
+    let mut commitments: Vec<[u8; 32]> = vec![];
+    for i in 0..3_u64 {
+        let res = poseidon.hash_two(&Fr::from(i), &Fr::one()).unwrap();
+        let mut element: [u8; 32] = [0u8; 32];
+        element.copy_from_slice(&res.into_repr().to_bytes_le());
+        commitments.push(element);
+
+        let info = mock_info(
+            "depositor",
+            &[Coin::new(1_000_000_u128, NATIVE_TOKEN_DENOM)],
+        );
+        let deposit_msg = DepositMsg {
+            commitment: Some(element),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Deposit(deposit_msg),
+        )
+        .unwrap();
+    }
+
+    // Query all the leaves
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: None,
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.start_index, 0);
+    assert_eq!(leaves_response.leaves, commitments);
+
+    // Query the page
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: None,
+            start_index: Some(1),
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.start_index, 1);
+    assert_eq!(leaves_response.leaves, vec![commitments[1]]);
+
+    // Query the leaf index of commitment
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LeafIndex {
+            commitment: commitments[2],
+        },
+    )
+    .unwrap();
+    let leaf_index_response: LeafIndexResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaf_index_response.leaf_index, 2);
+
+    // Unknown commitment
+    assert!(query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LeafIndex {
+            commitment: [0u8; 32]
+        },
+    )
+    .is_err());
+
+    // Query the merkle path of leaf and check it against the current root
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MerklePath {
+            tree_id: None,
+            leaf_index: 2,
+        },
+    )
+    .unwrap();
+    let path_response: MerklePathResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(path_response.siblings.len(), MERKLE_TREE_LEVELS as usize);
+    assert_eq!(path_response.path_bits[..2], [false, true]);
+    assert_eq!(
+        path_response.root,
+        crate::state::read_root(&deps.storage, 3).unwrap()
+    );
+
+    let hasher = protocol_cosmwasm::poseidon::Poseidon::new();
+    let mut node = commitments[2];
+    for (sibling, is_right) in path_response
+        .siblings
+        .iter()
+        .zip(path_response.path_bits.iter())
+    {
+        node = if *is_right {
+            hasher.hash(vec![*sibling, node]).unwrap()
+        } else {
+            hasher.hash(vec![node, *sibling]).unwrap()
+        };
+    }
+    assert_eq!(node, path_response.root);
+
+    // Current root should be known and the latest in the root history
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::IsKnownRoot {
+            root: path_response.root,
+        },
+    )
+    .unwrap();
+    let is_known_response: IsKnownRootResponse = from_binary(&query_bin).unwrap();
+    assert!(is_known_response.known);
+
+    let query_bin = query(deps.as_ref(), mock_env(), QueryMsg::RootHistory {}).unwrap();
+    let root_history_response: RootHistoryResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(root_history_response.roots.len(), 4);
+    assert_eq!(root_history_response.roots[0], path_response.root);
+    assert_eq!(
+        root_history_response.roots[3],
+        protocol_cosmwasm::zeroes::zeroes(MERKLE_TREE_LEVELS)
+    );
+
+    // Leaf not inserted yet
+    assert!(query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MerklePath {
+            tree_id: None,
+            leaf_index: 3,
+        },
+    )
+    .is_err());
+}
+
+#[test]
+fn test_mixer_migrate_should_start_merkle_node_tracking() {
+    let mut deps = create_mixer(MixerType::Native);
+
+    let params = setup_params(Curve::Bn254, 5, 3);
+    let poseidon = Poseidon::new(params);
+    let commitments: Vec<[u8; 32]> = (0..5_u64)
+        .map(|i| {
+            let res = poseidon.hash_two(&Fr::from(i), &Fr::one()).unwrap();
+            let mut element: [u8; 32] = [0u8; 32];
+            element.copy_from_slice(&res.into_repr().to_bytes_le());
+            element
+        })
+        .collect();
+    let deposit = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, commitment| {
+        let info = mock_info(
+            "depositor",
+            &[Coin::new(1_000_000_u128, NATIVE_TOKEN_DENOM)],
+        );
+        let deposit_msg = DepositMsg {
+            commitment: Some(commitment),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Deposit(deposit_msg),
+        )
+        .unwrap();
+    };
+    let merkle_path = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, leaf_index| {
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MerklePath {
+                tree_id: None,
+                leaf_index,
+            },
+        )
+        .map(|bin| from_binary::<MerklePathResponse>(&bin).unwrap())
+    };
+    let hasher = protocol_cosmwasm::poseidon::Poseidon::new();
+    let path_root = |leaf: [u8; 32], path: &MerklePathResponse| {
+        let mut node = leaf;
+        for (sibling, is_right) in path.siblings.iter().zip(path.path_bits.iter()) {
+            node = if *is_right {
+                hasher.hash(vec![*sibling, node]).unwrap()
+            } else {
+                hasher.hash(vec![node, *sibling]).unwrap()
+            };
+        }
+        node
+    };
+
+    // The pool deployed before the node tracking has no merkle nodes
+    for commitment in &commitments[..3] {
+        deposit(&mut deps, *commitment);
+    }
+    let keys = MERKLE_NODES
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()
+        .unwrap();
+    for key in keys {
+        MERKLE_NODES.remove(&mut deps.storage, key);
+    }
+
+    let _ = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    // The merkle paths of the earlier leaves are not available
+    let err = merkle_path(&deps, 1).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(ContractError::MerklePathUnavailable.to_string())
+    );
+
+    // The merkle paths of the next leaves are computed against the current root
+    for commitment in &commitments[3..] {
+        deposit(&mut deps, *commitment);
+    }
+    let _ = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let root = crate::state::read_root(&deps.storage, 5).unwrap();
+    for leaf_index in 3..5 {
+        let path = merkle_path(&deps, leaf_index).unwrap();
+        assert_eq!(path.root, root);
+        assert_eq!(path_root(commitments[leaf_index as usize], &path), root);
+    }
+
+    // The pool deployed with the node tracking keeps all the merkle paths
+    let mut deps = create_mixer(MixerType::Native);
+    for commitment in &commitments[..3] {
+        deposit(&mut deps, *commitment);
+    }
+    let _ = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let path = merkle_path(&deps, 0).unwrap();
+    assert_eq!(path_root(commitments[0], &path), path.root);
+}
+
+#[test]
+fn test_mixer_should_open_new_tree_when_full() {
+    let mut deps = mock_dependencies();
+    let instantiate_msg = InstantiateMsg {
+        merkletree_levels: 1,
+        deposit_size: Uint128::from_str(DEPOSIT_SIZE).unwrap(),
+        cw20_address: None,
+        native_token_denom: Some(NATIVE_TOKEN_DENOM.to_string()),
+    };
+    let _ = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        instantiate_msg,
+    )
+    .unwrap();
+
+    // Deposit 3 commitments into the trees of 2 leaves
+    let params = setup_params(Curve::Bn254, 5, 3);
+    let poseidon = Poseidon::new(params);
+    let mut commitments: Vec<[u8; 32]> = vec![];
+    let mut roots: Vec<[u8; 32]> = vec![];
+    for i in 0..3_u64 {
+        let res = poseidon.hash_two(&Fr::from(i), &Fr::one()).unwrap();
+        let mut element: [u8; 32] = [0u8; 32];
+        element.copy_from_slice(&res.into_repr().to_bytes_le());
+        commitments.push(element);
+
+        let info = mock_info(
+            "depositor",
+            &[Coin::new(1_000_000_u128, NATIVE_TOKEN_DENOM)],
+        );
+        let deposit_msg = DepositMsg {
+            commitment: Some(element),
+        };
+        let response = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Deposit(deposit_msg),
+        )
+        .unwrap();
+
+        let commitment_event = &response.events[1];
+        assert_eq!(
+            commitment_event.attributes[..2],
+            [
+                attr("tree_id", (i / 2).to_string()),
+                attr("leaf_index", (i % 2).to_string()),
+            ]
+        );
+        roots.push(crate::state::read_root(&deps.storage, i as u32 + 1).unwrap());
+    }
+
+    // The 3rd commitment is the first leaf of the new tree
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LeafIndex {
+            commitment: commitments[2],
+        },
+    )
+    .unwrap();
+    let leaf_index_response: LeafIndexResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaf_index_response.tree_id, 1);
+    assert_eq!(leaf_index_response.leaf_index, 0);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: Some(0),
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.leaves, commitments[..2].to_vec());
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MerklePath {
+            tree_id: Some(0),
+            leaf_index: 1,
+        },
+    )
+    .unwrap();
+    let path_response: MerklePathResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(path_response.root, roots[1]);
+
+    // The roots of the previous tree are still known
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::IsKnownRoot { root: roots[1] },
+    )
+    .unwrap();
+    let is_known_response: IsKnownRootResponse = from_binary(&query_bin).unwrap();
+    assert!(is_known_response.known);
 }
 
 #[test]
@@ -241,7 +865,7 @@ fn test_mixer_should_work_with_wasm_utils() {
         ExecuteMsg::Deposit(deposit_msg).clone(),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_element.0;
     assert_eq!(on_chain_root, local_root);
@@ -265,7 +889,7 @@ fn test_mixer_should_work_with_wasm_utils() {
         ExecuteMsg::Withdraw(withdraw_msg),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 }
 
 #[test]
@@ -291,7 +915,7 @@ fn test_mixer_fail_when_any_byte_is_changed_in_proof() {
         ExecuteMsg::Deposit(deposit_msg.clone()),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_element.0;
     assert_eq!(on_chain_root, local_root);
@@ -345,7 +969,7 @@ fn test_mixer_should_withdraw_native_token() {
         ExecuteMsg::Deposit(deposit_msg.clone()),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_element.0;
     assert_eq!(on_chain_root, local_root);
@@ -368,7 +992,35 @@ fn test_mixer_should_withdraw_native_token() {
         ExecuteMsg::Withdraw(withdraw_msg),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
+    assert_eq!(response.events[1].ty, "mixer-nullifier");
+    assert_eq!(
+        response.events[1].attributes,
+        vec![attr("nullifier", hex_encode(&nullifier_hash_element.0))]
+    );
+
+    // Nullifier should be marked as spent
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::IsKnownNullifier {
+            nullifier: nullifier_hash_element.0,
+        },
+    )
+    .unwrap();
+    let is_known_response: IsKnownNullifierResponse = from_binary(&query_bin).unwrap();
+    assert!(is_known_response.known);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SpentNullifiers {
+            nullifiers: vec![nullifier_hash_element.0, [1u8; 32]],
+        },
+    )
+    .unwrap();
+    let spent_response: SpentNullifiersResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(spent_response.spent, vec![true, false]);
 }
 
 #[test]
@@ -394,7 +1046,7 @@ fn test_mixer_should_fail_when_invalid_merkle_roots() {
         ExecuteMsg::Deposit(deposit_msg.clone()),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_element.0;
     assert_eq!(on_chain_root, local_root);
@@ -444,7 +1096,7 @@ fn test_mixer_should_withdraw_cw20_token() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_element.0;
@@ -469,7 +1121,7 @@ fn test_mixer_should_withdraw_cw20_token() {
         ExecuteMsg::Withdraw(withdraw_msg),
     )
     .unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let expected_recipient = RECIPIENT.to_string();
     let expected_messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -503,7 +1155,7 @@ fn test_mixer_should_fail_when_wrong_relayer_input() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_element.0;
@@ -553,7 +1205,7 @@ fn test_mixer_should_fail_when_fee_submitted_is_changed() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 2);
 
     let on_chain_root = crate::state::read_root(&deps.storage, 1).unwrap();
     let local_root = root_element.0;
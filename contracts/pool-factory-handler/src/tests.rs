@@ -0,0 +1,122 @@
+use crate::contract::*;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{attr, from_binary, to_binary, CosmosMsg, OwnedDeps, WasmMsg};
+
+use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::pool_factory::ExecuteMsg as PoolFactoryExecMsg;
+use protocol_cosmwasm::pool_factory_handler::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use protocol_cosmwasm::structs::BridgeAddrResponse;
+
+const BRIDGE_ADDR: &str = "bridge-contract";
+const RESOURCE_ID: [u8; 32] = [1u8; 32];
+const POOL_FACTORY_ADDR: &str = "pool-factory-contract";
+
+fn instantiate_pool_factory_handler() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    // Instantiate the "pool-factory-handler".
+    let msg = InstantiateMsg {
+        bridge_addr: BRIDGE_ADDR.to_string(),
+        initial_resource_ids: vec![RESOURCE_ID],
+        initial_contract_addresses: vec![POOL_FACTORY_ADDR.to_string()],
+    };
+    let info = mock_info("creator", &[]);
+    let _ = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps
+}
+
+fn proposal_to_exec_data(
+    resource_id: [u8; 32],
+    nonce: u32,
+    proposal: PoolFactoryExecMsg,
+) -> Vec<u8> {
+    let base64_encoded_proposal = to_binary(&proposal).unwrap().0;
+
+    let mut execution_data: Vec<u8> = vec![];
+    execution_data.extend_from_slice(&resource_id);
+    execution_data.extend_from_slice(&[0u8; 4]); // func_sig
+    execution_data.extend_from_slice(&nonce.to_be_bytes());
+    execution_data.extend_from_slice(&base64_encoded_proposal);
+    execution_data
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        bridge_addr: BRIDGE_ADDR.to_string(),
+        initial_resource_ids: vec![],
+        initial_contract_addresses: vec![],
+    };
+    let info = mock_info("creator", &[]);
+
+    // we can just call .unwrap() to assert this was a success
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(0, res.messages.len());
+    assert_eq!(res.attributes, vec![attr("method", "instantiate")]);
+
+    // it worked, let's query the state("bridge_addr")
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBridgeAddress {}).unwrap();
+    let bridge_addr_resp: BridgeAddrResponse = from_binary(&res).unwrap();
+    assert_eq!(bridge_addr_resp.bridge_addr, BRIDGE_ADDR.to_string());
+}
+
+#[test]
+fn test_handler_execute_proposal() {
+    // Instantiate the "pool-factory-handler"
+    let mut deps = instantiate_pool_factory_handler();
+
+    let proposal = PoolFactoryExecMsg::UpdatePoolAdmin {
+        pool: "mixer-pool".to_string(),
+        admin: "governance".to_string(),
+        nonce: 1,
+    };
+
+    // Fails to execute the proposal from non-bridge address
+    let info = mock_info("non-bridge", &[]);
+    let exec_proposal_msg = ExecuteMsg::ExecuteProposal {
+        resource_id: RESOURCE_ID,
+        data: proposal_to_exec_data(RESOURCE_ID, 1, proposal.clone()),
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, exec_proposal_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fails to execute the proposal if the "nonce" mismatches the one of header
+    let info = mock_info(BRIDGE_ADDR, &[]);
+    let exec_proposal_msg = ExecuteMsg::ExecuteProposal {
+        resource_id: RESOURCE_ID,
+        data: proposal_to_exec_data(RESOURCE_ID, 2, proposal.clone()),
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, exec_proposal_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidProposal);
+
+    // Fails to execute the proposal if the header is too short
+    let info = mock_info(BRIDGE_ADDR, &[]);
+    let exec_proposal_msg = ExecuteMsg::ExecuteProposal {
+        resource_id: RESOURCE_ID,
+        data: RESOURCE_ID.to_vec(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, exec_proposal_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidProposal);
+
+    // Forwards the proposal to the "pool-factory"
+    let info = mock_info(BRIDGE_ADDR, &[]);
+    let exec_proposal_msg = ExecuteMsg::ExecuteProposal {
+        resource_id: RESOURCE_ID,
+        data: proposal_to_exec_data(RESOURCE_ID, 1, proposal.clone()),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, exec_proposal_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: POOL_FACTORY_ADDR.to_string(),
+            msg: to_binary(&proposal).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(res.attributes, vec![attr("method", "exec_proposal")]);
+}
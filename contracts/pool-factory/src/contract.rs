@@ -0,0 +1,358 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    attr, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
+    StdError, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+
+use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::pool_factory::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, PoolInitMsg, PoolKind, PoolResponse, PoolsResponse,
+    QueryMsg,
+};
+
+use crate::state::{
+    has_pool, read_pool_address, save_pool, Config, PoolInfo, CONFIG, PENDING_POOL, POOLS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cosmwasm-pool-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INSTANTIATE_POOL_REPLY_ID: u64 = 1;
+
+// settings for pagination
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // Validation 1. Check if the funds are sent with this message
+    if !info.funds.is_empty() {
+        return Err(ContractError::UnnecessaryFunds {});
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // set config
+    let handler = deps.api.addr_validate(&msg.handler)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            handler,
+            proposal_nonce: 0_u32,
+            mixer_code_id: msg.mixer_code_id,
+            anchor_code_id: msg.anchor_code_id,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "instantiate")]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreatePool { pool, nonce } => create_pool(deps, env, info, pool, nonce),
+        ExecuteMsg::SetHandler { handler, nonce } => set_handler(deps, info, handler, nonce),
+        ExecuteMsg::MigratePool {
+            pool,
+            new_code_id,
+            msg,
+            nonce,
+        } => migrate_pool(deps, info, pool, new_code_id, msg, nonce),
+        ExecuteMsg::UpdatePoolAdmin { pool, admin, nonce } => {
+            update_pool_admin(deps, info, pool, admin, nonce)
+        }
+    }
+}
+
+// Validates the tx sender("handler") & "nonce" of the proposal.
+fn validate_proposal(deps: Deps, info: &MessageInfo, nonce: u32) -> Result<Config, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.handler {
+        return Err(ContractError::Unauthorized {});
+    }
+    let proposal_nonce = config.proposal_nonce;
+    if nonce <= proposal_nonce || proposal_nonce + 1048 < nonce {
+        return Err(ContractError::InvalidNonce);
+    }
+    Ok(config)
+}
+
+// Validates the "pool" address is registered in the factory.
+fn validate_pool(deps: Deps, pool: &str) -> Result<Addr, ContractError> {
+    let pool = deps.api.addr_validate(pool)?;
+    if !POOLS.has(deps.storage, pool.to_string()) {
+        return Err(ContractError::PoolNotFound);
+    }
+    Ok(pool)
+}
+
+/// Instantiates a new pool for the (asset, denomination)
+/// The pool is registered when the instantiation succeeds(see `reply`).
+fn create_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool: PoolInitMsg,
+    nonce: u32,
+) -> Result<Response, ContractError> {
+    // Validations
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+
+    let (pool_info, code_id, init_msg) = match pool {
+        PoolInitMsg::Mixer(msg) => {
+            let asset = match (&msg.native_token_denom, &msg.cw20_address) {
+                (Some(denom), None) => denom.to_string(),
+                (None, Some(cw20_address)) => deps.api.addr_validate(cw20_address)?.to_string(),
+                _ => return Err(ContractError::InvalidPoolAsset),
+            };
+            let pool_info = PoolInfo {
+                kind: PoolKind::Mixer,
+                asset,
+                deposit_size: msg.deposit_size,
+            };
+            (pool_info, config.mixer_code_id, to_binary(&msg)?)
+        }
+        PoolInitMsg::Anchor(msg) => {
+            let pool_info = PoolInfo {
+                kind: PoolKind::Anchor,
+                asset: deps.api.addr_validate(&msg.tokenwrapper_addr)?.to_string(),
+                deposit_size: msg.deposit_size,
+            };
+            (pool_info, config.anchor_code_id, to_binary(&msg)?)
+        }
+    };
+
+    if has_pool(
+        deps.storage,
+        pool_info.kind,
+        &pool_info.asset,
+        pool_info.deposit_size,
+    ) {
+        return Err(ContractError::PoolAlreadyExists);
+    }
+
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_POOL.save(deps.storage, &pool_info)?;
+
+    let label = format!(
+        "{}-{}-{}",
+        pool_info.kind.as_str(),
+        pool_info.asset,
+        pool_info.deposit_size
+    );
+    let instantiate_msg = SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin: Some(env.contract.address.to_string()),
+            code_id,
+            msg: init_msg,
+            funds: vec![],
+            label,
+        },
+        INSTANTIATE_POOL_REPLY_ID,
+    );
+
+    Ok(Response::new()
+        .add_submessage(instantiate_msg)
+        .add_attributes(vec![
+            attr("action", "create_pool"),
+            attr("kind", pool_info.kind.as_str()),
+            attr("asset", pool_info.asset),
+            attr("deposit_size", pool_info.deposit_size),
+            attr("nonce", nonce.to_string()),
+        ]))
+}
+
+/// Sets a new handler for the contract
+fn set_handler(
+    deps: DepsMut,
+    info: MessageInfo,
+    handler: String,
+    nonce: u32,
+) -> Result<Response, ContractError> {
+    // Validations
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+
+    // Save a new "handler"
+    let new_handler = deps.api.addr_validate(&handler)?;
+    config.handler = new_handler;
+    config.proposal_nonce = nonce;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_handler"),
+        attr("handler", handler),
+        attr("nonce", nonce.to_string()),
+    ]))
+}
+
+/// Migrates the registered pool to the new code id
+fn migrate_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool: String,
+    new_code_id: u64,
+    msg: Binary,
+    nonce: u32,
+) -> Result<Response, ContractError> {
+    // Validations
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+    let pool = validate_pool(deps.as_ref(), &pool)?;
+
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Migrate {
+            contract_addr: pool.to_string(),
+            new_code_id,
+            msg,
+        })
+        .add_attributes(vec![
+            attr("action", "migrate_pool"),
+            attr("pool", pool),
+            attr("new_code_id", new_code_id.to_string()),
+            attr("nonce", nonce.to_string()),
+        ]))
+}
+
+/// Updates the admin of the registered pool
+fn update_pool_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool: String,
+    admin: String,
+    nonce: u32,
+) -> Result<Response, ContractError> {
+    // Validations
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+    let pool = validate_pool(deps.as_ref(), &pool)?;
+    let admin = deps.api.addr_validate(&admin)?;
+
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::UpdateAdmin {
+            contract_addr: pool.to_string(),
+            admin: admin.to_string(),
+        })
+        .add_attributes(vec![
+            attr("action", "update_pool_admin"),
+            attr("pool", pool),
+            attr("admin", admin),
+            attr("nonce", nonce.to_string()),
+        ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_POOL_REPLY_ID => register_pool(deps, msg),
+        id => Err(ContractError::Std(StdError::generic_err(format!(
+            "Unknown reply id: {}",
+            id
+        )))),
+    }
+}
+
+/// Registers the pending pool with the address of instantiated contract
+fn register_pool(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let response = msg.result.into_result().map_err(StdError::generic_err)?;
+    let pool_addr = response
+        .events
+        .iter()
+        .filter(|event| event.ty == "instantiate")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attribute| attribute.key == "_contract_address")
+        .map(|attribute| attribute.value.to_string())
+        .ok_or_else(|| StdError::generic_err("Cannot find the instantiated pool address"))?;
+    let pool_addr: Addr = deps.api.addr_validate(&pool_addr)?;
+
+    let pool_info = PENDING_POOL.load(deps.storage)?;
+    PENDING_POOL.remove(deps.storage);
+    save_pool(deps.storage, &pool_addr, &pool_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_pool"),
+        attr("kind", pool_info.kind.as_str()),
+        attr("asset", pool_info.asset),
+        attr("deposit_size", pool_info.deposit_size),
+        attr("pool", pool_addr),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&get_config(deps)?),
+        QueryMsg::Pool {
+            kind,
+            asset,
+            deposit_size,
+        } => to_binary(&get_pool(deps, kind, asset, deposit_size)?),
+        QueryMsg::Pools { start_after, limit } => to_binary(&get_pools(deps, start_after, limit)?),
+    }
+}
+
+fn get_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        handler: config.handler.to_string(),
+        proposal_nonce: config.proposal_nonce,
+        mixer_code_id: config.mixer_code_id,
+        anchor_code_id: config.anchor_code_id,
+    })
+}
+
+fn get_pool(
+    deps: Deps,
+    kind: PoolKind,
+    asset: String,
+    deposit_size: Uint128,
+) -> StdResult<PoolResponse> {
+    let address = read_pool_address(deps.storage, kind, &asset, deposit_size)?;
+    Ok(PoolResponse {
+        kind,
+        asset,
+        deposit_size,
+        address: address.to_string(),
+    })
+}
+
+fn get_pools(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PoolsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let pools = POOLS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(address, pool)| PoolResponse {
+                kind: pool.kind,
+                asset: pool.asset,
+                deposit_size: pool.deposit_size,
+                address,
+            })
+        })
+        .collect::<StdResult<Vec<PoolResponse>>>()?;
+    Ok(PoolsResponse { pools })
+}
@@ -0,0 +1,62 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+
+use protocol_cosmwasm::pool_factory::PoolKind;
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Pool being instantiated, which is registered on "reply"
+pub const PENDING_POOL: Item<PoolInfo> = Item::new("pending_pool");
+
+/// Registered pools, keyed by the pool address
+pub const POOLS: Map<String, PoolInfo> = Map::new("pools");
+/// Pool addresses, keyed by the `pool_key`
+pub const POOL_ADDRESSES: Map<String, Addr> = Map::new("pool_addresses");
+
+/// Config
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Config {
+    pub handler: Addr,
+    pub proposal_nonce: u32,
+    pub mixer_code_id: u64,
+    pub anchor_code_id: u64,
+}
+
+/// PoolInfo
+///   "kind"          Type of pool("mixer" or "anchor")
+///   "asset"         Native token denom or cw20 token address of the pool
+///   "deposit_size"  Denomination of the pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PoolInfo {
+    pub kind: PoolKind,
+    pub asset: String,
+    pub deposit_size: Uint128,
+}
+
+pub fn pool_key(kind: PoolKind, asset: &str, deposit_size: Uint128) -> String {
+    format!("{}:{}:{}", kind.as_str(), asset, deposit_size)
+}
+
+pub fn read_pool_address(
+    store: &dyn Storage,
+    kind: PoolKind,
+    asset: &str,
+    deposit_size: Uint128,
+) -> StdResult<Addr> {
+    POOL_ADDRESSES.load(store, pool_key(kind, asset, deposit_size))
+}
+
+pub fn has_pool(store: &dyn Storage, kind: PoolKind, asset: &str, deposit_size: Uint128) -> bool {
+    POOL_ADDRESSES.has(store, pool_key(kind, asset, deposit_size))
+}
+
+pub fn save_pool(store: &mut dyn Storage, address: &Addr, pool: &PoolInfo) -> StdResult<()> {
+    POOLS.save(store, address.to_string(), pool)?;
+    POOL_ADDRESSES.save(
+        store,
+        pool_key(pool.kind, &pool.asset, pool.deposit_size),
+        address,
+    )
+}
@@ -0,0 +1,396 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, Binary, CosmosMsg, Event, OwnedDeps, Reply, SubMsgResponse,
+    SubMsgResult, Uint128, WasmMsg,
+};
+
+use protocol_cosmwasm::anchor::InstantiateMsg as AnchorInstantiateMsg;
+use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::mixer::InstantiateMsg as MixerInstantiateMsg;
+use protocol_cosmwasm::pool_factory::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, PoolInitMsg, PoolKind, PoolResponse, PoolsResponse,
+    QueryMsg,
+};
+
+use crate::contract::{execute, instantiate, query, reply};
+
+const HANDLER: &str = "pool-factory-handler";
+const MIXER_CODE_ID: u64 = 1;
+const ANCHOR_CODE_ID: u64 = 2;
+const NATIVE_TOKEN_DENOM: &str = "uusd";
+const TOKENWRAPPER_ADDR: &str = "terra1340t6lqq6jxhm8d6gtz0hzz5jzcszvm27urkn2";
+const MERKLE_TREE_LEVELS: u32 = 30;
+
+fn create_pool_factory() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    let init_msg = InstantiateMsg {
+        handler: HANDLER.to_string(),
+        mixer_code_id: MIXER_CODE_ID,
+        anchor_code_id: ANCHOR_CODE_ID,
+    };
+
+    // Should pass this "unwrap" if success.
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("governor", &[]),
+        init_msg,
+    )
+    .unwrap();
+    deps
+}
+
+fn mixer_init_msg(deposit_size: u128) -> MixerInstantiateMsg {
+    MixerInstantiateMsg {
+        deposit_size: Uint128::from(deposit_size),
+        merkletree_levels: MERKLE_TREE_LEVELS,
+        native_token_denom: Some(NATIVE_TOKEN_DENOM.to_string()),
+        cw20_address: None,
+    }
+}
+
+fn instantiate_reply(pool_addr: &str) -> Reply {
+    Reply {
+        id: 1,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![Event::new("instantiate")
+                .add_attribute("_contract_address", pool_addr)
+                .add_attribute("code_id", MIXER_CODE_ID.to_string())],
+            data: None,
+        }),
+    }
+}
+
+#[test]
+fn test_pool_factory_proper_initialization() {
+    let deps = create_pool_factory();
+
+    // Check the config
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_resp: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config_resp,
+        ConfigResponse {
+            handler: HANDLER.to_string(),
+            proposal_nonce: 0,
+            mixer_code_id: MIXER_CODE_ID,
+            anchor_code_id: ANCHOR_CODE_ID,
+        }
+    );
+}
+
+#[test]
+fn test_pool_factory_create_pool() {
+    let mut deps = create_pool_factory();
+
+    // Fails to "create pool" if tx sender is not current handler addr
+    let info = mock_info("anyone", &[]);
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        pool: PoolInitMsg::Mixer(mixer_init_msg(1_000_000)),
+        nonce: 1,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, create_pool_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fails to "create pool" if the mixer asset is not unique
+    let info = mock_info(HANDLER, &[]);
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        pool: PoolInitMsg::Mixer(MixerInstantiateMsg {
+            cw20_address: Some(TOKENWRAPPER_ADDR.to_string()),
+            ..mixer_init_msg(1_000_000)
+        }),
+        nonce: 1,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, create_pool_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPoolAsset);
+
+    // Succeed to "create pool"
+    let info = mock_info(HANDLER, &[]);
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        pool: PoolInitMsg::Mixer(mixer_init_msg(1_000_000)),
+        nonce: 1,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, create_pool_msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, msg, .. }) => {
+            assert_eq!(*code_id, MIXER_CODE_ID);
+            assert_eq!(*msg, to_binary(&mixer_init_msg(1_000_000)).unwrap());
+        }
+        _ => panic!("Unexpected message"),
+    }
+
+    // Registers the pool on "reply"
+    let res = reply(deps.as_mut(), mock_env(), instantiate_reply("mixer-uusd-1")).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "register_pool"),
+            attr("kind", "mixer"),
+            attr("asset", NATIVE_TOKEN_DENOM),
+            attr("deposit_size", "1000000"),
+            attr("pool", "mixer-uusd-1"),
+        ]
+    );
+
+    // Fails to "create pool" if the pool already exists
+    let info = mock_info(HANDLER, &[]);
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        pool: PoolInitMsg::Mixer(mixer_init_msg(1_000_000)),
+        nonce: 2,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, create_pool_msg).unwrap_err();
+    assert_eq!(err, ContractError::PoolAlreadyExists);
+
+    // Succeed to "create pool" for other denomination & kind
+    let info = mock_info(HANDLER, &[]);
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        pool: PoolInitMsg::Mixer(mixer_init_msg(10_000_000)),
+        nonce: 2,
+    };
+    let _ = execute(deps.as_mut(), mock_env(), info, create_pool_msg).unwrap();
+    let _ = reply(
+        deps.as_mut(),
+        mock_env(),
+        instantiate_reply("mixer-uusd-10"),
+    )
+    .unwrap();
+
+    let info = mock_info(HANDLER, &[]);
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        pool: PoolInitMsg::Anchor(AnchorInstantiateMsg {
+            max_edges: 2,
+            levels: MERKLE_TREE_LEVELS,
+            deposit_size: Uint128::from(1_000_000_u128),
+            tokenwrapper_addr: TOKENWRAPPER_ADDR.to_string(),
+            handler: "anchor-handler".to_string(),
+        }),
+        nonce: 3,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, create_pool_msg).unwrap();
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, .. }) => {
+            assert_eq!(*code_id, ANCHOR_CODE_ID)
+        }
+        _ => panic!("Unexpected message"),
+    }
+    let _ = reply(deps.as_mut(), mock_env(), instantiate_reply("anchor-1")).unwrap();
+
+    // Check the registry
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Pool {
+            kind: PoolKind::Mixer,
+            asset: NATIVE_TOKEN_DENOM.to_string(),
+            deposit_size: Uint128::from(10_000_000_u128),
+        },
+    )
+    .unwrap();
+    let pool: PoolResponse = from_binary(&res).unwrap();
+    assert_eq!(pool.address, "mixer-uusd-10".to_string());
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Pools {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let pools: PoolsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pools
+            .pools
+            .iter()
+            .map(|p| p.address.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["anchor-1", "mixer-uusd-1", "mixer-uusd-10"]
+    );
+    assert_eq!(pools.pools[0].kind, PoolKind::Anchor);
+    assert_eq!(pools.pools[0].asset, TOKENWRAPPER_ADDR.to_string());
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Pools {
+            start_after: Some("anchor-1".to_string()),
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    let pools: PoolsResponse = from_binary(&res).unwrap();
+    assert_eq!(pools.pools.len(), 1);
+    assert_eq!(pools.pools[0].address, "mixer-uusd-1".to_string());
+}
+
+#[test]
+fn test_pool_factory_set_handler() {
+    let new_handler: &str = "new-handler-address";
+    let nonce: u32 = 2u32;
+
+    let mut deps = create_pool_factory();
+
+    // Fails to "set handler" if tx sender is not current handler addr
+    let info = mock_info("anyone", &[]);
+    let set_handler_msg = ExecuteMsg::SetHandler {
+        handler: new_handler.to_string(),
+        nonce,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, set_handler_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fails to "set handler" if "nonce" is too big or small
+    let info = mock_info(HANDLER, &[]);
+    let set_handler_msg = ExecuteMsg::SetHandler {
+        handler: new_handler.to_string(),
+        nonce: nonce + 2000,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, set_handler_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidNonce);
+
+    // Succeed to "set handler"
+    let info = mock_info(HANDLER, &[]);
+    let set_handler_msg = ExecuteMsg::SetHandler {
+        handler: new_handler.to_string(),
+        nonce,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, set_handler_msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "set_handler"),
+            attr("handler", new_handler),
+            attr("nonce", nonce.to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_pool_factory_migrate_pool() {
+    let mut deps = create_pool_factory();
+
+    // Register a pool
+    let info = mock_info(HANDLER, &[]);
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        pool: PoolInitMsg::Mixer(mixer_init_msg(1_000_000)),
+        nonce: 1,
+    };
+    let _ = execute(deps.as_mut(), mock_env(), info, create_pool_msg).unwrap();
+    let _ = reply(deps.as_mut(), mock_env(), instantiate_reply("mixer-uusd-1")).unwrap();
+
+    // Fails to "migrate pool" if tx sender is not current handler addr
+    let info = mock_info("anyone", &[]);
+    let migrate_pool_msg = ExecuteMsg::MigratePool {
+        pool: "mixer-uusd-1".to_string(),
+        new_code_id: 3,
+        msg: Binary::from(b"{}".to_vec()),
+        nonce: 2,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, migrate_pool_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fails to "migrate pool" if the pool is not registered
+    let info = mock_info(HANDLER, &[]);
+    let migrate_pool_msg = ExecuteMsg::MigratePool {
+        pool: "unknown-pool".to_string(),
+        new_code_id: 3,
+        msg: Binary::from(b"{}".to_vec()),
+        nonce: 2,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, migrate_pool_msg).unwrap_err();
+    assert_eq!(err, ContractError::PoolNotFound);
+
+    // Succeed to "migrate pool"
+    let info = mock_info(HANDLER, &[]);
+    let migrate_pool_msg = ExecuteMsg::MigratePool {
+        pool: "mixer-uusd-1".to_string(),
+        new_code_id: 3,
+        msg: Binary::from(b"{}".to_vec()),
+        nonce: 2,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, migrate_pool_msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr: "mixer-uusd-1".to_string(),
+            new_code_id: 3,
+            msg: Binary::from(b"{}".to_vec()),
+        })
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate_pool"),
+            attr("pool", "mixer-uusd-1"),
+            attr("new_code_id", "3"),
+            attr("nonce", "2"),
+        ]
+    );
+
+    // Fails to replay the same "nonce"
+    let info = mock_info(HANDLER, &[]);
+    let migrate_pool_msg = ExecuteMsg::MigratePool {
+        pool: "mixer-uusd-1".to_string(),
+        new_code_id: 3,
+        msg: Binary::from(b"{}".to_vec()),
+        nonce: 2,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, migrate_pool_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidNonce);
+}
+
+#[test]
+fn test_pool_factory_update_pool_admin() {
+    let new_admin: &str = "governance-account";
+
+    let mut deps = create_pool_factory();
+
+    // Register a pool
+    let info = mock_info(HANDLER, &[]);
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        pool: PoolInitMsg::Mixer(mixer_init_msg(1_000_000)),
+        nonce: 1,
+    };
+    let _ = execute(deps.as_mut(), mock_env(), info, create_pool_msg).unwrap();
+    let _ = reply(deps.as_mut(), mock_env(), instantiate_reply("mixer-uusd-1")).unwrap();
+
+    // Fails to "update pool admin" if the pool is not registered
+    let info = mock_info(HANDLER, &[]);
+    let update_admin_msg = ExecuteMsg::UpdatePoolAdmin {
+        pool: "unknown-pool".to_string(),
+        admin: new_admin.to_string(),
+        nonce: 2,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, update_admin_msg).unwrap_err();
+    assert_eq!(err, ContractError::PoolNotFound);
+
+    // Succeed to "update pool admin"
+    let info = mock_info(HANDLER, &[]);
+    let update_admin_msg = ExecuteMsg::UpdatePoolAdmin {
+        pool: "mixer-uusd-1".to_string(),
+        admin: new_admin.to_string(),
+        nonce: 2,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, update_admin_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+            contract_addr: "mixer-uusd-1".to_string(),
+            admin: new_admin.to_string(),
+        })
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "update_pool_admin"),
+            attr("pool", "mixer-uusd-1"),
+            attr("admin", new_admin),
+            attr("nonce", "2"),
+        ]
+    );
+}
@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, WasmMsg,
+    attr, from_slice, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, WasmMsg,
 };
 use cw2::set_contract_version;
 use protocol_cosmwasm::utils::element_encoder;
@@ -15,6 +15,7 @@ use protocol_cosmwasm::error::ContractError;
 use protocol_cosmwasm::structs::{
     BridgeAddrResponse, ContractAddrResponse, ResourceIdResponse, WhitelistCheckResponse,
 };
+use protocol_cosmwasm::token_wrapper::ExecuteMsg as TokenWrapperExecuteMsg;
 use protocol_cosmwasm::tokenwrapper_handler::{
     ExecuteMsg, InstantiateMsg, QueryMsg, UpdateRecordResponse,
 };
@@ -131,10 +132,18 @@ fn execute_proposal(
     data: Vec<u8>,
 ) -> Result<Response, ContractError> {
     // Parse the (proposal)`data`.
+    if data.len() < 40 {
+        return Err(ContractError::InvalidProposal);
+    }
     let parsed_resource_id = element_encoder(&data[0..32]);
     let _func_sig = &data[32..36];
-    let _nonce = &data[36..40];
-    let base64_encoded_proposal = &data[40..];
+    let nonce = u32::from_be_bytes(
+        data[36..40]
+            .try_into()
+            .map_err(|_| ContractError::InvalidProposal)?,
+    );
+    let proposal: TokenWrapperExecuteMsg =
+        from_slice(&data[40..]).map_err(|_| ContractError::InvalidProposal)?;
 
     let bridge_addr = STATE.load(deps.storage)?.bridge_addr;
 
@@ -154,10 +163,15 @@ fn execute_proposal(
         }));
     }
 
+    // Only the governance entries, whose "nonce" matches the one of proposal header, are executed.
+    if proposal.proposal_nonce() != Some(nonce as u64) {
+        return Err(ContractError::InvalidProposal);
+    }
+
     // Execute the proposal according to function signature
     let msgs = vec![CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: tokenwrapper_addr.to_string(),
-        msg: Binary::from(base64_encoded_proposal),
+        msg: to_binary(&proposal)?,
         funds: vec![],
     })];
 
@@ -2,7 +2,7 @@ use crate::contract::*;
 use cosmwasm_std::testing::{
     mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
-use cosmwasm_std::{attr, from_binary, to_binary, OwnedDeps};
+use cosmwasm_std::{attr, from_binary, to_binary, OwnedDeps, Uint128};
 
 use protocol_cosmwasm::error::ContractError;
 use protocol_cosmwasm::structs::BridgeAddrResponse;
@@ -28,11 +28,17 @@ fn instantiate_tokenwrapper_handler() -> OwnedDeps<MockStorage, MockApi, MockQue
     deps
 }
 
-fn proposal_to_exec_data(resource_id: [u8; 32], proposal: GovernedTokenWrapperExecMsg) -> Vec<u8> {
+fn proposal_to_exec_data(
+    resource_id: [u8; 32],
+    nonce: u32,
+    proposal: GovernedTokenWrapperExecMsg,
+) -> Vec<u8> {
     let base64_encoded_proposal = to_binary(&proposal).unwrap().0;
 
     let mut execution_data: Vec<u8> = vec![];
     execution_data.extend_from_slice(&resource_id);
+    execution_data.extend_from_slice(&[0u8; 4]); // func_sig
+    execution_data.extend_from_slice(&nonce.to_be_bytes());
     execution_data.extend_from_slice(&base64_encoded_proposal);
     execution_data
 }
@@ -123,8 +129,9 @@ fn test_handler_execute_proposal() {
 
     let set_handler_proposal = GovernedTokenWrapperExecMsg::ConfigureNativeAllowed {
         is_native_allowed: Some(true),
+        nonce: 1,
     };
-    let exec_data = proposal_to_exec_data(RESOURCE_ID, set_handler_proposal);
+    let exec_data = proposal_to_exec_data(RESOURCE_ID, 1, set_handler_proposal);
     let exec_proposal_msg = ExecuteMsg::ExecuteProposal {
         resource_id: RESOURCE_ID,
         data: exec_data,
@@ -132,4 +139,30 @@ fn test_handler_execute_proposal() {
 
     let res = execute(deps.as_mut(), mock_env(), info, exec_proposal_msg).unwrap();
     assert_eq!(res.messages.len(), 1);
+
+    // Fails to execute the proposal if the "nonce" mismatches the one of header
+    let info = mock_info(BRIDGE_ADDR, &[]);
+    let proposal = GovernedTokenWrapperExecMsg::ConfigureNativeAllowed {
+        is_native_allowed: Some(false),
+        nonce: 3,
+    };
+    let exec_proposal_msg = ExecuteMsg::ExecuteProposal {
+        resource_id: RESOURCE_ID,
+        data: proposal_to_exec_data(RESOURCE_ID, 2, proposal),
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, exec_proposal_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidProposal);
+
+    // Fails to execute the non-governance entry
+    let info = mock_info(BRIDGE_ADDR, &[]);
+    let proposal = GovernedTokenWrapperExecMsg::Transfer {
+        recipient: "attacker".to_string(),
+        amount: Uint128::from(100_u128),
+    };
+    let exec_proposal_msg = ExecuteMsg::ExecuteProposal {
+        resource_id: RESOURCE_ID,
+        data: proposal_to_exec_data(RESOURCE_ID, 2, proposal),
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, exec_proposal_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidProposal);
 }
@@ -3,12 +3,13 @@ use std::str::FromStr;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coins, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+    attr, coins, from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut,
+    Env, MessageInfo, Order, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
 use cw20_base::allowances::{
     execute_burn_from, execute_decrease_allowance, execute_increase_allowance, execute_send_from,
     execute_transfer_from, query_allowance,
@@ -16,25 +17,37 @@ use cw20_base::allowances::{
 use cw20_base::contract::{
     execute_burn, execute_mint, execute_send, execute_transfer, query_balance, query_token_info,
 };
+use cw20_base::enumerable::{query_all_accounts, query_all_allowances};
 
 use cw20_base::state::{MinterData, TokenInfo, TOKEN_INFO};
 
 use protocol_cosmwasm::error::ContractError;
 use protocol_cosmwasm::token_wrapper::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, FeeFromAmountResponse, GetAmountToWrapResponse,
-    InstantiateMsg, QueryMsg, WRAP_FEE_CALC_DENOMINATOR,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, FeeFromAmountResponse, FeeFromUnwrapAmountResponse,
+    GetAmountToWrapResponse, InstantiateMsg, MigrateMsg, NativeDenomsResponse, QueryMsg, Reserve,
+    ReservesResponse, TokenConfigResponse, TokensResponse, TrustedCallersResponse,
+    WRAP_FEE_CALC_DENOMINATOR,
 };
 
-use crate::state::{Config, CONFIG, HISTORICAL_TOKENS, TOKENS};
+use crate::state::{
+    decrease_reserve, increase_reserve, migrate_config, read_decimals, read_fee_percentage,
+    read_reserve, Config, CONFIG, FEE_PERCENTAGES, HISTORICAL_NATIVE_TOKENS, HISTORICAL_TOKENS,
+    MAX_SHARES, NATIVE_TOKENS, RESERVES, TOKENS, TOKEN_DECIMALS, TRUSTED_CALLERS,
+};
 use crate::utils::{
-    get_amount_to_wrap, get_fee_from_amount, is_valid_address, is_valid_unwrap_amount,
-    is_valid_wrap_amount,
+    get_amount_to_wrap, get_fee_from_amount, get_unwrap_fee_and_refund, is_authorized_caller,
+    is_valid_address, is_valid_asset_share, is_valid_native_denom, is_valid_unwrap_amount,
+    is_valid_wrap_amount, split_unwrap_fee, to_underlying_amount, to_wrapped_amount,
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cosmwasm-tokenwrapper";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// pagination info for token queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -63,6 +76,10 @@ pub fn instantiate(
         Some(v) => deps.api.addr_validate(v.as_str())?,
         None => info.sender,
     };
+    let handler = match msg.handler {
+        Some(v) => deps.api.addr_validate(v.as_str())?,
+        None => governor.clone(),
+    };
     let fee_recipient = deps.api.addr_validate(msg.fee_recipient.as_str())?;
     if msg.fee_percentage > WRAP_FEE_CALC_DENOMINATOR {
         return Err(ContractError::Std(StdError::generic_err(
@@ -74,15 +91,22 @@ pub fn instantiate(
         deps.storage,
         &Config {
             governor,
+            handler,
             fee_recipient,
             fee_percentage,
-            native_token_denom: msg.native_token_denom,
+            native_token_denom: msg.native_token_denom.clone(),
             is_native_allowed: msg.is_native_allowed,
             wrapping_limit: msg.wrapping_limit,
             proposal_nonce: 0_u64,
+            unwrap_fee_percentage: 0,
+            is_unwrap_fee_retained: false,
         },
     )?;
 
+    // Add the default native token denom to wrapping list
+    NATIVE_TOKENS.save(deps.storage, msg.native_token_denom.clone(), &true)?;
+    HISTORICAL_NATIVE_TOKENS.save(deps.storage, msg.native_token_denom, &true)?;
+
     Ok(Response::default())
 }
 
@@ -96,50 +120,72 @@ pub fn execute(
     match msg {
         /* -------  TokenWrapper functionality ------------ */
         // Used to wrap native tokens on behalf of a sender.
-        ExecuteMsg::Wrap { sender, recipient } => wrap_native(deps, env, info, sender, recipient),
+        ExecuteMsg::Wrap { sender, recipient } => {
+            wrap_native(deps, env, info, sender, recipient, None)
+        }
+
+        // Used to wrap native tokens & send them to a contract with the payload.
+        ExecuteMsg::WrapAndCall { contract, msg } => {
+            wrap_native(deps, env, info, None, None, Some((contract, msg)))
+        }
 
         // Used to unwrap native/cw20 tokens on behalf of a sender.
         ExecuteMsg::Unwrap {
             sender,
             token,
+            denom,
             amount,
             recipient,
-        } => match token {
+        } => match (token, denom) {
             // Unwrap the cw20 tokens.
-            Some(token) => unwrap_cw20(deps, env, info, sender, token, amount, recipient),
+            (Some(token), None) => unwrap_cw20(deps, env, info, sender, token, amount, recipient),
             // Unwrap the native token.
-            None => unwrap_native(deps, env, info, sender, amount, recipient),
+            (None, denom) => unwrap_native(deps, env, info, sender, denom, amount, recipient),
+            (Some(_), Some(_)) => Err(ContractError::Std(StdError::GenericErr {
+                msg: "Only one of token & denom can be given".to_string(),
+            })),
         },
 
+        // Used to unwrap into the basket of underlying assets, proportionally to the reserves.
+        ExecuteMsg::UnwrapProRata { amount, recipient } => {
+            unwrap_pro_rata(deps, env, info, amount, recipient)
+        }
+
         // Used to wrap cw20 tokens on behalf of a sender.
         ExecuteMsg::Receive(msg) => wrap_cw20(deps, env, info, msg),
         /* ------------------------------------- */
 
         /* -----  Governance functionality ----- */
         // Update the `governor`. Only the governor can execute this entry.
-        ExecuteMsg::ConfigureGovernor { governor } => {
-            update_config(deps, info, governor, None, None, None, None)
-        }
-
-        // Update the `is_native_allowed`. Only the governor can execute this entry.
-        ExecuteMsg::ConfigureNativeAllowed { is_native_allowed } => {
-            update_config(deps, info, None, is_native_allowed, None, None, None)
-        }
-
-        // Update the `wrapping_limit`. Only the owner can execute this entry.
-        ExecuteMsg::ConfigureWrappingLimit { wrapping_limit } => {
-            update_config(deps, info, None, None, wrapping_limit, None, None)
-        }
-
-        // Update the `fee_percentage`. Only the owner can execute this entry.
-        ExecuteMsg::ConfigureFeePercentage { fee_percentage } => {
-            update_config(deps, info, None, None, None, fee_percentage, None)
-        }
+        ExecuteMsg::ConfigureGovernor { governor } => configure_governor(deps, info, governor),
+
+        // Set the `handler`. The current handler or the governor(emergency) can execute this entry.
+        ExecuteMsg::SetHandler { handler, nonce } => set_handler(deps, info, handler, nonce),
+
+        // Below entries are executed by the handler with the "nonce".
+        // Update the `is_native_allowed`.
+        ExecuteMsg::ConfigureNativeAllowed {
+            is_native_allowed,
+            nonce,
+        } => update_config(deps, info, is_native_allowed, None, None, None, nonce),
+
+        // Update the `wrapping_limit`.
+        ExecuteMsg::ConfigureWrappingLimit {
+            wrapping_limit,
+            nonce,
+        } => update_config(deps, info, None, wrapping_limit, None, None, nonce),
+
+        // Update the `fee_percentage`.
+        ExecuteMsg::ConfigureFeePercentage {
+            fee_percentage,
+            nonce,
+        } => update_config(deps, info, None, None, fee_percentage, None, nonce),
 
-        // Update the `wrapping_limit`. Only the owner can execute this entry.
-        ExecuteMsg::ConfigureFeeRecipient { fee_recipient } => {
-            update_config(deps, info, None, None, None, None, fee_recipient)
-        }
+        // Update the `fee_recipient`.
+        ExecuteMsg::ConfigureFeeRecipient {
+            fee_recipient,
+            nonce,
+        } => update_config(deps, info, None, None, None, fee_recipient, nonce),
 
         // Add new cw20 token address to wrapping list
         ExecuteMsg::AddCw20TokenAddr { token, nonce } => add_token_addr(deps, info, token, nonce),
@@ -148,6 +194,52 @@ pub fn execute(
         ExecuteMsg::RemoveCw20TokenAddr { token, nonce } => {
             remove_token_addr(deps, info, token, nonce)
         }
+
+        // Add new native token denom to wrapping list
+        ExecuteMsg::AddNativeTokenDenom {
+            denom,
+            decimals,
+            nonce,
+        } => add_native_denom(deps, info, denom, decimals, nonce),
+
+        // Remove native token denom from wrapping list (disallow wrapping)
+        ExecuteMsg::RemoveNativeTokenDenom { denom, nonce } => {
+            remove_native_denom(deps, info, denom, nonce)
+        }
+
+        // Update the unwrap fee
+        ExecuteMsg::ConfigureUnwrapFee {
+            unwrap_fee_percentage,
+            is_unwrap_fee_retained,
+            nonce,
+        } => configure_unwrap_fee(
+            deps,
+            info,
+            unwrap_fee_percentage,
+            is_unwrap_fee_retained,
+            nonce,
+        ),
+
+        // Add/remove the trusted caller(eg: anchor, vanchor) for on-behalf-of wrap/unwrap
+        ExecuteMsg::ConfigureTrustedCaller {
+            caller,
+            is_trusted,
+            nonce,
+        } => configure_trusted_caller(deps, info, caller, is_trusted, nonce),
+
+        // Set the wrapping fee percentage of the underlying asset
+        ExecuteMsg::ConfigureTokenFeePercentage {
+            token,
+            fee_percentage,
+            nonce,
+        } => configure_token_fee_percentage(deps, info, token, fee_percentage, nonce),
+
+        // Set the max share of the underlying asset in the reserves
+        ExecuteMsg::ConfigureTokenWrappingLimit {
+            token,
+            max_share,
+            nonce,
+        } => configure_token_wrapping_limit(deps, info, token, max_share, nonce),
         /* --------------------------------------- */
         // These all come from cw20-base to implement the cw20 standard
         ExecuteMsg::Transfer { recipient, amount } => {
@@ -200,6 +292,7 @@ fn wrap_native(
     info: MessageInfo,
     sender: Option<String>,
     recipient: Option<String>,
+    call: Option<(String, Binary)>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -210,22 +303,60 @@ fn wrap_native(
         }));
     }
 
-    // Validate the wrapping amount
-    let wrapping_amount = info
-        .funds
-        .iter()
-        .find(|token| token.denom == *config.native_token_denom)
-        .ok_or(ContractError::InsufficientFunds {})?
-        .amount;
+    // Validate the wrapping denoms & amount
+    if info.funds.is_empty() {
+        return Err(ContractError::InsufficientFunds {});
+    }
+    // The wrapping amount is scaled to the wrapped token decimals.
+    let mut wrapping_amount = Uint128::zero();
+    for coin in info.funds.iter() {
+        if !is_valid_native_denom(deps.as_ref(), &coin.denom) {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: format!("Invalid native token denom({})", coin.denom),
+            }));
+        }
+        wrapping_amount = wrapping_amount
+            .checked_add(to_wrapped_amount(deps.storage, &coin.denom, coin.amount)?)
+            .map_err(StdError::from)?;
+    }
     if wrapping_amount.is_zero() || !is_valid_wrap_amount(deps.branch(), wrapping_amount) {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: "Invalid native token amount".to_string(),
         }));
     }
 
-    // Calculate the "fee" & "amount_to_wrap".
-    let cost_to_wrap = get_fee_from_amount(wrapping_amount, config.fee_percentage);
-    let left_over = wrapping_amount - cost_to_wrap;
+    // Calculate the "fee" & "amount_to_wrap" of each denom.
+    let mut fees: Vec<Coin> = vec![];
+    let mut cost_to_wrap = Uint128::zero();
+    let mut minted = Uint128::zero();
+    for coin in info.funds.iter() {
+        let fee_percentage = read_fee_percentage(deps.storage, &config, &coin.denom)?;
+        let fee = get_fee_from_amount(coin.amount, fee_percentage);
+        let amount = coin.amount - fee;
+
+        // Scale the "amount" to the wrapped token decimals(rounding down)
+        let minted_amount = to_wrapped_amount(deps.storage, &coin.denom, amount)?;
+        if minted_amount.is_zero() {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: "Invalid native token amount".to_string(),
+            }));
+        }
+
+        // Validate the share of native token in the reserves
+        if !is_valid_asset_share(deps.as_ref(), &coin.denom, minted_amount)? {
+            return Err(ContractError::MaxShareExceeded);
+        }
+        increase_reserve(deps.storage, &coin.denom, amount)?;
+
+        if !fee.is_zero() {
+            fees.push(Coin {
+                denom: coin.denom.clone(),
+                amount: fee,
+            });
+        }
+        cost_to_wrap += fee;
+        minted += minted_amount;
+    }
 
     // call into cw20-base to mint the token, call as self as no one else is allowed
     let sub_info = MessageInfo {
@@ -235,40 +366,49 @@ fn wrap_native(
 
     // Mint the wrapped tokens to "sender" address.
     let sender = sender.unwrap_or_else(|| info.sender.to_string());
-    execute_mint(
-        deps.branch(),
-        env.clone(),
-        sub_info,
-        sender.clone(),
-        left_over,
-    )?;
+    execute_mint(deps.branch(), env.clone(), sub_info, sender.clone(), minted)?;
 
     // Send the wrapped tokens to "recipient" address if any.
-    if recipient.is_some() {
-        let sub_info = MessageInfo {
-            sender: deps.api.addr_validate(sender.as_str())?,
-            funds: vec![],
-        };
-        execute_transfer(deps, env, sub_info, recipient.clone().unwrap(), left_over)?;
+    if let Some(recipient) = recipient.clone() {
+        transfer_on_behalf_of(
+            deps.branch(),
+            env.clone(),
+            info.sender.clone(),
+            &sender,
+            recipient,
+            minted,
+        )?;
     }
 
     // send "fee" to fee_recipient
     let mut msgs: Vec<CosmosMsg> = vec![];
-    if !cost_to_wrap.is_zero() {
+    if !fees.is_empty() {
         msgs.push(CosmosMsg::Bank(BankMsg::Send {
             to_address: config.fee_recipient.to_string(),
-            amount: coins(cost_to_wrap.u128(), config.native_token_denom),
+            amount: fees,
         }));
     }
 
-    Ok(Response::new().add_messages(msgs).add_attributes(vec![
-        attr("action", "wrap_native"),
-        attr("from", info.sender),
-        attr("owner", sender.clone()),
-        attr("to", recipient.unwrap_or(sender)),
-        attr("minted", left_over),
-        attr("fee", cost_to_wrap),
-    ]))
+    // Send the wrapped tokens to "contract" with the payload if any.
+    let (to, send_msgs) = match call {
+        Some((contract, msg)) => {
+            let send_msgs = send_on_behalf_of(deps, env, &sender, &contract, minted, msg)?;
+            (contract, send_msgs)
+        }
+        None => (recipient.unwrap_or_else(|| sender.clone()), vec![]),
+    };
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_submessages(send_msgs)
+        .add_attributes(vec![
+            attr("action", "wrap_native"),
+            attr("from", info.sender),
+            attr("owner", sender),
+            attr("to", to),
+            attr("minted", minted),
+            attr("fee", cost_to_wrap),
+        ]))
 }
 
 fn unwrap_native(
@@ -276,6 +416,7 @@ fn unwrap_native(
     env: Env,
     info: MessageInfo,
     sender: Option<String>,
+    denom: Option<String>,
     amount: Uint128,
     recipient: Option<String>,
 ) -> Result<Response, ContractError> {
@@ -288,6 +429,14 @@ fn unwrap_native(
         }));
     }
 
+    // Validate the "denom"
+    let denom = denom.unwrap_or_else(|| config.native_token_denom.clone());
+    if !HISTORICAL_NATIVE_TOKENS.has(deps.storage, denom.clone()) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: format!("Invalid native token denom({})", denom),
+        }));
+    }
+
     // Validate the "amount"
     if !is_valid_unwrap_amount(deps.branch(), &sender, amount) {
         return Err(ContractError::Std(StdError::GenericErr {
@@ -295,19 +444,42 @@ fn unwrap_native(
         }));
     }
 
-    // burn from the "sender"
-    let sub_info = MessageInfo {
-        sender: deps.api.addr_validate(sender.as_str())?,
-        funds: vec![],
+    // Calculate the unwrap "fee" & "refund", scaled to the native token decimals(rounding down).
+    // The retained "fee" is kept in the reserves.
+    let (fee, refund) = get_unwrap_fee_and_refund(deps.storage, &denom, amount)?;
+    if refund.is_zero() && fee.is_zero() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Too small unwrap amount for the native token decimals".to_string(),
+        }));
+    }
+    let released = if config.is_unwrap_fee_retained {
+        refund
+    } else {
+        refund + fee
     };
-    execute_burn(deps.branch(), env, sub_info, amount)?;
+    if read_reserve(deps.storage, &denom)? < released {
+        return Err(ContractError::InsufficientReserve);
+    }
+    decrease_reserve(deps.storage, &denom, released)?;
+
+    // burn from the "sender"
+    burn_on_behalf_of(deps.branch(), env, info.sender.clone(), &sender, amount)?;
 
-    // Send the native token to "recipient"
+    // Send the native token to "recipient" & the "fee" to "fee_recipient"
     let recipient = recipient.unwrap_or_else(|| sender.clone());
-    let msgs: Vec<CosmosMsg> = vec![CosmosMsg::Bank(BankMsg::Send {
-        to_address: recipient.clone(),
-        amount: coins(amount.u128(), config.native_token_denom),
-    })];
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    if !refund.is_zero() {
+        msgs.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.clone(),
+            amount: coins(refund.u128(), denom.clone()),
+        }));
+    }
+    if !fee.is_zero() && !config.is_unwrap_fee_retained {
+        msgs.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: config.fee_recipient.to_string(),
+            amount: coins(fee.u128(), denom),
+        }));
+    }
 
     Ok(Response::new().add_messages(msgs).add_attributes(vec![
         attr("action", "unwrap_native"),
@@ -315,7 +487,8 @@ fn unwrap_native(
         attr("owner", sender),
         attr("to", recipient),
         attr("unwrap", amount),
-        attr("refund", amount),
+        attr("refund", refund),
+        attr("fee", fee),
     ]))
 }
 
@@ -345,23 +518,51 @@ fn unwrap_cw20(
         }));
     }
 
-    // burn from the "sender"
-    let sub_info = MessageInfo {
-        sender: deps.api.addr_validate(sender.as_str())?,
-        funds: vec![],
+    // Calculate the unwrap "fee" & "refund", scaled to the cw20 token decimals(rounding down).
+    // The retained "fee" is kept in the reserves.
+    let config = CONFIG.load(deps.storage)?;
+    let (fee, refund) = get_unwrap_fee_and_refund(deps.storage, token.as_str(), amount)?;
+    if refund.is_zero() && fee.is_zero() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Too small unwrap amount for the cw20 token decimals".to_string(),
+        }));
+    }
+    let released = if config.is_unwrap_fee_retained {
+        refund
+    } else {
+        refund + fee
     };
-    execute_burn(deps.branch(), env, sub_info, amount)?;
+    if read_reserve(deps.storage, token.as_str())? < released {
+        return Err(ContractError::InsufficientReserve);
+    }
+    decrease_reserve(deps.storage, token.as_str(), released)?;
 
-    // Send the Cw20 token to "recipient"
+    // burn from the "sender"
+    burn_on_behalf_of(deps.branch(), env, info.sender.clone(), &sender, amount)?;
+
+    // Send the Cw20 token to "recipient" & the "fee" to "fee_recipient"
     let recipient = recipient.unwrap_or_else(|| sender.clone());
-    let msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: token.to_string(),
-        funds: vec![],
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: recipient.clone(),
-            amount,
-        })?,
-    })];
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    if !refund.is_zero() {
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.clone(),
+                amount: refund,
+            })?,
+        }));
+    }
+    if !fee.is_zero() && !config.is_unwrap_fee_retained {
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: config.fee_recipient.to_string(),
+                amount: fee,
+            })?,
+        }));
+    }
 
     Ok(Response::new().add_messages(msgs).add_attributes(vec![
         attr("action", "unwrap_cw20"),
@@ -369,7 +570,134 @@ fn unwrap_cw20(
         attr("owner", sender),
         attr("to", recipient),
         attr("unwrap", amount),
-        attr("refund", amount),
+        attr("refund", refund),
+        attr("fee", fee),
+    ]))
+}
+
+fn unwrap_pro_rata(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let sender = info.sender.to_string();
+    let config = CONFIG.load(deps.storage)?;
+
+    // Validate the "amount"
+    if amount.is_zero() || !is_valid_unwrap_amount(deps.branch(), &sender, amount) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: format!("Insufficient token balance for sender({})", &sender),
+        }));
+    }
+
+    // Calculate the payouts of every underlying asset, rounding down.
+    // The rounding dust is kept in the reserves.
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+    let payouts = RESERVES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(asset, reserve)| (asset, reserve.multiply_ratio(amount, total_supply)))
+        })
+        .filter(|item| !matches!(item, Ok((_, payout)) if payout.is_zero()))
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+    if payouts.is_empty() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Too small unwrap amount for the reserves".to_string(),
+        }));
+    }
+
+    // Validate the "is_native_allowed"
+    let has_native_payout = payouts
+        .iter()
+        .any(|(asset, _)| HISTORICAL_NATIVE_TOKENS.has(deps.storage, asset.to_string()));
+    if has_native_payout && !config.is_native_allowed {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Unwrapping native token is not allowed in this token wrapper".to_string(),
+        }));
+    }
+
+    // burn from the "sender"
+    burn_on_behalf_of(deps.branch(), env, info.sender.clone(), &sender, amount)?;
+
+    // Send the native tokens in single message & the cw20 tokens to "recipient",
+    // and the unwrap "fee" of every payout to "fee_recipient".
+    // The retained "fee" is kept in the reserves.
+    let recipient = recipient.unwrap_or_else(|| sender.clone());
+    let mut native_refunds: Vec<Coin> = vec![];
+    let mut native_fees: Vec<Coin> = vec![];
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut refunds: Vec<String> = vec![];
+    let mut fees: Vec<String> = vec![];
+    for (asset, payout) in payouts.iter() {
+        let (fee, refund) = split_unwrap_fee(deps.storage, *payout)?;
+        let released = if config.is_unwrap_fee_retained {
+            refund
+        } else {
+            *payout
+        };
+        decrease_reserve(deps.storage, asset, released)?;
+        refunds.push(format!("{}{}", refund, asset));
+        fees.push(format!("{}{}", fee, asset));
+
+        let is_fee_sent = !fee.is_zero() && !config.is_unwrap_fee_retained;
+        if HISTORICAL_NATIVE_TOKENS.has(deps.storage, asset.to_string()) {
+            if !refund.is_zero() {
+                native_refunds.push(Coin::new(refund.u128(), asset));
+            }
+            if is_fee_sent {
+                native_fees.push(Coin::new(fee.u128(), asset));
+            }
+        } else {
+            if !refund.is_zero() {
+                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: asset.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.clone(),
+                        amount: refund,
+                    })?,
+                }));
+            }
+            if is_fee_sent {
+                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: asset.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: config.fee_recipient.to_string(),
+                        amount: fee,
+                    })?,
+                }));
+            }
+        }
+    }
+    if !native_fees.is_empty() {
+        msgs.insert(
+            0,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: config.fee_recipient.to_string(),
+                amount: native_fees,
+            }),
+        );
+    }
+    if !native_refunds.is_empty() {
+        msgs.insert(
+            0,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.clone(),
+                amount: native_refunds,
+            }),
+        );
+    }
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        attr("action", "unwrap_pro_rata"),
+        attr("from", info.sender),
+        attr("to", recipient),
+        attr("unwrap", amount),
+        attr("payouts", refunds.join(",")),
+        attr("fees", fees.join(",")),
     ]))
 }
 
@@ -388,9 +716,11 @@ fn wrap_cw20(
         }));
     }
 
-    // Validate the cw20 token amount.
+    // Validate the cw20 token amount(scaled to the wrapped token decimals).
     let cw20_token_amount = cw20_msg.amount;
-    if cw20_token_amount.is_zero() || !is_valid_wrap_amount(deps.branch(), cw20_token_amount) {
+    let wrapping_amount =
+        to_wrapped_amount(deps.storage, cw20_address.as_str(), cw20_token_amount)?;
+    if cw20_token_amount.is_zero() || !is_valid_wrap_amount(deps.branch(), wrapping_amount) {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: "Invalid cw20 token".to_string(),
         }));
@@ -398,80 +728,233 @@ fn wrap_cw20(
 
     // Calculate the "fee" & "amount_to_wrap".
     let config = CONFIG.load(deps.storage)?;
-    let cost_to_wrap = get_fee_from_amount(cw20_msg.amount, config.fee_percentage);
+    let fee_percentage = read_fee_percentage(deps.storage, &config, cw20_address.as_str())?;
+    let cost_to_wrap = get_fee_from_amount(cw20_msg.amount, fee_percentage);
     let left_over = cw20_msg.amount - cost_to_wrap;
 
-    match from_binary(&cw20_msg.msg) {
-        Ok(Cw20HookMsg::Wrap { sender, recipient }) => {
-            // call into cw20-base to mint the token, call as self as no one else is allowed
-            let sub_info = MessageInfo {
-                sender: env.contract.address.clone(),
-                funds: vec![],
-            };
-            let sender = sender.unwrap_or_else(|| cw20_msg.sender.clone());
-            execute_mint(
-                deps.branch(),
-                env.clone(),
-                sub_info,
-                sender.clone(),
-                left_over,
-            )?;
-
-            // Send the wrapped tokens to "recipient" address if any.
-            if recipient.is_some() {
-                let sub_info = MessageInfo {
-                    sender: deps.api.addr_validate(sender.as_str())?,
-                    funds: vec![],
-                };
-                execute_transfer(deps, env, sub_info, recipient.clone().unwrap(), left_over)?;
-            }
+    // Scale the "left_over" to the wrapped token decimals(rounding down)
+    let minted = to_wrapped_amount(deps.storage, cw20_address.as_str(), left_over)?;
+    if minted.is_zero() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Invalid cw20 token".to_string(),
+        }));
+    }
 
-            // Send the "fee" to "fee_recipient".
-            let mut msgs: Vec<CosmosMsg> = vec![];
-            if !cost_to_wrap.is_zero() {
-                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: cw20_address.to_string(),
-                    funds: vec![],
-                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                        recipient: config.fee_recipient.to_string(),
-                        amount: cost_to_wrap,
-                    })?,
-                }));
-            }
+    // Validate the share of cw20 token in the reserves
+    if !is_valid_asset_share(deps.as_ref(), cw20_address.as_str(), minted)? {
+        return Err(ContractError::MaxShareExceeded);
+    }
+
+    let (sender, recipient, call) = match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Wrap { sender, recipient } => (sender, recipient, None),
+        Cw20HookMsg::WrapAndCall { contract, msg } => (None, None, Some((contract, msg))),
+    };
+
+    // call into cw20-base to mint the token, call as self as no one else is allowed
+    let sub_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    let sender = sender.unwrap_or_else(|| cw20_msg.sender.clone());
+    execute_mint(deps.branch(), env.clone(), sub_info, sender.clone(), minted)?;
+    increase_reserve(deps.storage, cw20_address.as_str(), left_over)?;
+
+    // Send the wrapped tokens to "recipient" address if any.
+    if let Some(recipient) = recipient.clone() {
+        let caller = deps.api.addr_validate(cw20_msg.sender.as_str())?;
+        transfer_on_behalf_of(
+            deps.branch(),
+            env.clone(),
+            caller,
+            &sender,
+            recipient,
+            minted,
+        )?;
+    }
+
+    // Send the "fee" to "fee_recipient".
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    if !cost_to_wrap.is_zero() {
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: cw20_address.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: config.fee_recipient.to_string(),
+                amount: cost_to_wrap,
+            })?,
+        }));
+    }
 
-            Ok(Response::new().add_messages(msgs).add_attributes(vec![
-                attr("action", "wrap_cw20"),
-                attr("from", cw20_msg.sender),
-                attr("owner", sender.clone()),
-                attr("to", recipient.unwrap_or(sender)),
-                attr("minted", left_over),
-                attr("fee", cost_to_wrap),
-            ]))
+    // Send the wrapped tokens to "contract" with the payload if any.
+    let (to, send_msgs) = match call {
+        Some((contract, msg)) => {
+            let send_msgs = send_on_behalf_of(deps, env, &sender, &contract, minted, msg)?;
+            (contract, send_msgs)
         }
-        Err(e) => Err(ContractError::Std(e)),
+        None => (recipient.unwrap_or_else(|| sender.clone()), vec![]),
+    };
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_submessages(send_msgs)
+        .add_attributes(vec![
+            attr("action", "wrap_cw20"),
+            attr("from", cw20_msg.sender),
+            attr("owner", sender),
+            attr("to", to),
+            attr("minted", minted),
+            attr("fee", cost_to_wrap),
+        ]))
+}
+
+// Burns the "amount" of wrapped tokens from the "owner".
+// If the "caller" is neither the "owner" nor a trusted caller,
+// the allowance of "owner" for the "caller" is consumed(same as "BurnFrom").
+fn burn_on_behalf_of(
+    deps: DepsMut,
+    env: Env,
+    caller: Addr,
+    owner: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if is_authorized_caller(deps.as_ref(), &caller, owner) {
+        let sub_info = MessageInfo {
+            sender: deps.api.addr_validate(owner)?,
+            funds: vec![],
+        };
+        execute_burn(deps, env, sub_info, amount)?;
+    } else {
+        let sub_info = MessageInfo {
+            sender: caller,
+            funds: vec![],
+        };
+        execute_burn_from(deps, env, sub_info, owner.to_string(), amount)?;
     }
+    Ok(())
 }
 
-fn update_config(
+// Transfers the "amount" of wrapped tokens from the "owner" to the "recipient".
+// If the "caller" is neither the "owner" nor a trusted caller,
+// the allowance of "owner" for the "caller" is consumed(same as "TransferFrom").
+fn transfer_on_behalf_of(
+    deps: DepsMut,
+    env: Env,
+    caller: Addr,
+    owner: &str,
+    recipient: String,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if is_authorized_caller(deps.as_ref(), &caller, owner) {
+        let sub_info = MessageInfo {
+            sender: deps.api.addr_validate(owner)?,
+            funds: vec![],
+        };
+        execute_transfer(deps, env, sub_info, recipient, amount)?;
+    } else {
+        let sub_info = MessageInfo {
+            sender: caller,
+            funds: vec![],
+        };
+        execute_transfer_from(deps, env, sub_info, owner.to_string(), recipient, amount)?;
+    }
+    Ok(())
+}
+
+// Sends the "amount" of wrapped tokens of the "owner" to the "contract" with the "msg"(same as "Send").
+// Returns the sub messages, which execute the "Receive" hook of the "contract".
+fn send_on_behalf_of(
+    deps: DepsMut,
+    env: Env,
+    owner: &str,
+    contract: &str,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let sub_info = MessageInfo {
+        sender: deps.api.addr_validate(owner)?,
+        funds: vec![],
+    };
+    let res = execute_send(deps, env, sub_info, contract.to_string(), amount, msg)?;
+    Ok(res.messages)
+}
+
+// Validates the tx sender(handler) & "nonce" of the proposal.
+// Returns the config, whose "proposal_nonce" should be updated & saved by the caller.
+fn validate_proposal(deps: Deps, info: &MessageInfo, nonce: u64) -> Result<Config, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    // Validate the tx sender.
+    if config.handler != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Validate the "nonce" value
+    if nonce <= config.proposal_nonce || config.proposal_nonce + 1048 < nonce {
+        return Err(ContractError::InvalidNonce);
+    }
+    Ok(config)
+}
+
+fn configure_governor(
     deps: DepsMut,
     info: MessageInfo,
     governor: Option<String>,
-    is_native_allowed: Option<bool>,
-    wrapping_limit: Option<Uint128>,
-    fee_percentage: Option<u16>,
-    fee_recipient: Option<String>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
     // Validate the tx sender.
-    if config.governor != deps.api.addr_validate(info.sender.as_str())? {
+    if config.governor != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
-    // Update the config
     if let Some(new_governor) = governor {
         config.governor = deps.api.addr_validate(&new_governor)?;
     }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("method", "update_config")]))
+}
+
+fn set_handler(
+    deps: DepsMut,
+    info: MessageInfo,
+    handler: String,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    // Validate the tx sender.
+    // The governor can replace the handler in emergency(eg: compromised handler).
+    if config.handler != info.sender && config.governor != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Validate the "nonce" value
+    if nonce <= config.proposal_nonce || config.proposal_nonce + 1048 < nonce {
+        return Err(ContractError::InvalidNonce);
+    }
+
+    // Save a new "handler" & "proposal_nonce"
+    config.handler = deps.api.addr_validate(&handler)?;
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_handler"),
+        attr("handler", handler),
+        attr("nonce", nonce.to_string()),
+    ]))
+}
+
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    is_native_allowed: Option<bool>,
+    wrapping_limit: Option<Uint128>,
+    fee_percentage: Option<u16>,
+    fee_recipient: Option<String>,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
 
+    // Update the config
     if let Some(is_native_allowed) = is_native_allowed {
         config.is_native_allowed = is_native_allowed;
     }
@@ -493,7 +976,8 @@ fn update_config(
         config.fee_recipient = deps.api.addr_validate(&fee_recipient)?;
     }
 
-    // Save the new config
+    // Save the new config & "proposal_nonce"
+    config.proposal_nonce = nonce;
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(vec![attr("method", "update_config")]))
@@ -512,16 +996,14 @@ fn add_token_addr(
         }));
     }
 
-    // Validate the tx sender.
-    let mut config = CONFIG.load(deps.storage)?;
-    if config.governor != deps.api.addr_validate(info.sender.as_str())? {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Validate the tx sender & "nonce"
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
 
-    // Validate the "nonce" value
-    if nonce <= config.proposal_nonce || config.proposal_nonce + 1048 < nonce {
-        return Err(ContractError::InvalidNonce);
-    }
+    // Record the decimals of "token"
+    let token_info: TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(token_addr.to_string(), &Cw20QueryMsg::TokenInfo {})?;
+    TOKEN_DECIMALS.save(deps.storage, token_addr.to_string(), &token_info.decimals)?;
 
     // Add the "token" to wrapping list
     TOKENS.save(deps.storage, token_addr.clone(), &true)?;
@@ -554,16 +1036,8 @@ fn remove_token_addr(
         }));
     }
 
-    // Validate the tx sender.
-    let mut config = CONFIG.load(deps.storage)?;
-    if config.governor != deps.api.addr_validate(info.sender.as_str())? {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    // Validate the "nonce" value
-    if nonce <= config.proposal_nonce || config.proposal_nonce + 1048 < nonce {
-        return Err(ContractError::InvalidNonce);
-    }
+    // Validate the tx sender & "nonce"
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
 
     // Remove the "token" from wrapping list
     TOKENS.save(deps.storage, token_addr.clone(), &false)?;
@@ -578,6 +1052,225 @@ fn remove_token_addr(
     ]))
 }
 
+fn add_native_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    decimals: u8,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    if denom.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Native token denom must not be empty",
+        )));
+    }
+    if is_valid_native_denom(deps.as_ref(), &denom) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Denom must not be valid".to_string(),
+        }));
+    }
+    // The reserves & wrapped tokens of the denom ever added are scaled by its recorded decimals.
+    if HISTORICAL_NATIVE_TOKENS.has(deps.storage, denom.clone())
+        && read_decimals(deps.storage, &denom)? != decimals
+    {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Decimals must be same as the recorded decimals of denom",
+        )));
+    }
+
+    // Validate the tx sender & "nonce"
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+
+    // Record the "decimals" of "denom"
+    TOKEN_DECIMALS.save(deps.storage, denom.clone(), &decimals)?;
+
+    // Add the "denom" to wrapping list
+    NATIVE_TOKENS.save(deps.storage, denom.clone(), &true)?;
+    HISTORICAL_NATIVE_TOKENS.save(deps.storage, denom.clone(), &true)?;
+
+    // Save the "proposal_nonce"
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "add_native_denom"),
+        attr("denom", denom),
+        attr("decimals", decimals.to_string()),
+    ]))
+}
+
+fn remove_native_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    if !is_valid_native_denom(deps.as_ref(), &denom) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Denom must be valid".to_string(),
+        }));
+    }
+
+    // Validate the tx sender & "nonce"
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+
+    // The default native token denom is always wrappable.
+    if denom == config.native_token_denom {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Default native token denom cannot be removed",
+        )));
+    }
+
+    // Remove the "denom" from wrapping list
+    NATIVE_TOKENS.save(deps.storage, denom.clone(), &false)?;
+
+    // Save the "proposal_nonce"
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "remove_native_denom"),
+        attr("denom", denom),
+    ]))
+}
+
+fn configure_unwrap_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    unwrap_fee_percentage: u16,
+    is_unwrap_fee_retained: bool,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    // Validate the tx sender & "nonce"
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+
+    if unwrap_fee_percentage > WRAP_FEE_CALC_DENOMINATOR {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Fee percentage cannot be greater than 10000",
+        )));
+    }
+
+    // Save the unwrap fee & "proposal_nonce"
+    config.unwrap_fee_percentage = unwrap_fee_percentage;
+    config.is_unwrap_fee_retained = is_unwrap_fee_retained;
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "configure_unwrap_fee"),
+        attr("unwrap_fee_percentage", unwrap_fee_percentage.to_string()),
+        attr("is_unwrap_fee_retained", is_unwrap_fee_retained.to_string()),
+    ]))
+}
+
+fn configure_trusted_caller(
+    deps: DepsMut,
+    info: MessageInfo,
+    caller: String,
+    is_trusted: bool,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    // Validate the tx sender & "nonce"
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+
+    let caller_addr = deps.api.addr_validate(caller.as_str())?;
+    TRUSTED_CALLERS.save(deps.storage, caller_addr.clone(), &is_trusted)?;
+
+    // Save the "proposal_nonce"
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "configure_trusted_caller"),
+        attr("caller", caller_addr.to_string()),
+        attr("is_trusted", is_trusted.to_string()),
+    ]))
+}
+
+fn configure_token_fee_percentage(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: Option<String>,
+    fee_percentage: Option<u16>,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    // Validate the tx sender & "nonce"
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+
+    let asset = read_asset(deps.as_ref(), token)?;
+    match fee_percentage {
+        Some(fee_percentage) => {
+            if fee_percentage > WRAP_FEE_CALC_DENOMINATOR {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "Fee percentage cannot be greater than 10000",
+                )));
+            }
+            FEE_PERCENTAGES.save(deps.storage, asset.clone(), &fee_percentage)?;
+        }
+        None => FEE_PERCENTAGES.remove(deps.storage, asset.clone()),
+    }
+
+    // Save the "proposal_nonce"
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "configure_token_fee_percentage"),
+        attr("asset", asset),
+        attr(
+            "fee_percentage",
+            fee_percentage.map_or("default".to_string(), |v| v.to_string()),
+        ),
+    ]))
+}
+
+fn configure_token_wrapping_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: Option<String>,
+    max_share: Option<u16>,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    // Validate the tx sender & "nonce"
+    let mut config = validate_proposal(deps.as_ref(), &info, nonce)?;
+
+    let asset = read_asset(deps.as_ref(), token)?;
+    match max_share {
+        Some(max_share) => {
+            if max_share > WRAP_FEE_CALC_DENOMINATOR {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "Max share cannot be greater than 10000",
+                )));
+            }
+            MAX_SHARES.save(deps.storage, asset.clone(), &max_share)?;
+        }
+        None => MAX_SHARES.remove(deps.storage, asset.clone()),
+    }
+
+    // Save the "proposal_nonce"
+    config.proposal_nonce = nonce;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "configure_token_wrapping_limit"),
+        attr("asset", asset),
+        attr(
+            "max_share",
+            max_share.map_or("none".to_string(), |v| v.to_string()),
+        ),
+    ]))
+}
+
+// Returns the key of underlying asset, which is either the native token denom or the cw20 token address.
+// The default native token denom is used if "token" is not given.
+fn read_asset(deps: Deps, token: Option<String>) -> StdResult<String> {
+    match token {
+        Some(token) if HISTORICAL_NATIVE_TOKENS.has(deps.storage, token.clone()) => Ok(token),
+        Some(token) => Ok(deps.api.addr_validate(&token)?.to_string()),
+        None => Ok(CONFIG.load(deps.storage)?.native_token_denom),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -585,13 +1278,54 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
 
         // Query the fee amount, calculated from wrap amount
-        QueryMsg::FeeFromAmount { amount_to_wrap } => {
-            to_binary(&query_fee_from_amount(deps, amount_to_wrap)?)
-        }
+        QueryMsg::FeeFromAmount {
+            amount_to_wrap,
+            token,
+        } => to_binary(&query_fee_from_amount(deps, amount_to_wrap, token)?),
 
         // Query the real wrap amount, calculated from total amount
-        QueryMsg::GetAmountToWrap { target_amount } => {
-            to_binary(&query_amount_to_wrap(deps, target_amount)?)
+        QueryMsg::GetAmountToWrap {
+            target_amount,
+            token,
+        } => to_binary(&query_amount_to_wrap(deps, target_amount, token)?),
+
+        // Query the unwrap fee & refund, calculated from unwrap amount
+        QueryMsg::FeeFromUnwrapAmount {
+            amount_to_unwrap,
+            token,
+        } => to_binary(&query_fee_from_unwrap_amount(
+            deps,
+            amount_to_unwrap,
+            token,
+        )?),
+
+        // Query the wrapping fee & limit of the underlying asset
+        QueryMsg::TokenConfig { token } => to_binary(&query_token_config(deps, token)?),
+
+        // Query the trusted callers for on-behalf-of wrap/unwrap
+        QueryMsg::TrustedCallers {} => to_binary(&query_trusted_callers(deps)?),
+
+        // Query the reserves of underlying assets
+        QueryMsg::Reserves {} => to_binary(&query_reserves(deps)?),
+
+        // Query the cw20 tokens allowed to wrap
+        QueryMsg::Tokens { start_after, limit } => {
+            to_binary(&query_tokens(deps, start_after, limit, false)?)
+        }
+
+        // Query the cw20 tokens ever added to wrapping list(unwrappable)
+        QueryMsg::HistoricalTokens { start_after, limit } => {
+            to_binary(&query_tokens(deps, start_after, limit, true)?)
+        }
+
+        // Query the native token denoms allowed to wrap
+        QueryMsg::NativeDenoms { start_after, limit } => {
+            to_binary(&query_native_denoms(deps, start_after, limit, false)?)
+        }
+
+        // Query the native token denoms ever added to wrapping list(unwrappable)
+        QueryMsg::HistoricalNativeDenoms { start_after, limit } => {
+            to_binary(&query_native_denoms(deps, start_after, limit, true)?)
         }
 
         // inherited from cw20-base
@@ -600,6 +1334,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Allowance { owner, spender } => {
             to_binary(&query_allowance(deps, owner, spender)?)
         }
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_all_allowances(deps, owner, start_after, limit)?),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_binary(&query_all_accounts(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -607,31 +1349,196 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
         governor: config.governor.to_string(),
+        handler: config.handler.to_string(),
         native_token_denom: config.native_token_denom,
         fee_recipient: config.fee_recipient.to_string(),
         fee_percentage: config.fee_percentage.to_string(),
         is_native_allowed: config.is_native_allowed.to_string(),
         wrapping_limit: config.wrapping_limit.to_string(),
         proposal_nonce: config.proposal_nonce.to_string(),
+        unwrap_fee_percentage: config.unwrap_fee_percentage.to_string(),
+        is_unwrap_fee_retained: config.is_unwrap_fee_retained.to_string(),
     })
 }
 
-fn query_fee_from_amount(deps: Deps, amount_to_wrap: String) -> StdResult<FeeFromAmountResponse> {
+fn query_fee_from_amount(
+    deps: Deps,
+    amount_to_wrap: String,
+    token: Option<String>,
+) -> StdResult<FeeFromAmountResponse> {
     let config = CONFIG.load(deps.storage)?;
+    let asset = read_asset(deps, token)?;
+    let fee_percentage = read_fee_percentage(deps.storage, &config, &asset)?;
     let amount_to_wrap = Uint128::from_str(&amount_to_wrap)?;
-    let fee_amt = get_fee_from_amount(amount_to_wrap, config.fee_percentage);
+    let fee_amt = get_fee_from_amount(amount_to_wrap, fee_percentage);
     Ok(FeeFromAmountResponse {
         amount_to_wrap: amount_to_wrap.to_string(),
         fee_amt: fee_amt.to_string(),
     })
 }
 
-fn query_amount_to_wrap(deps: Deps, target_amount: String) -> StdResult<GetAmountToWrapResponse> {
+fn query_fee_from_unwrap_amount(
+    deps: Deps,
+    amount_to_unwrap: String,
+    token: Option<String>,
+) -> StdResult<FeeFromUnwrapAmountResponse> {
+    let asset = read_asset(deps, token)?;
+    let amount_to_unwrap = Uint128::from_str(&amount_to_unwrap)?;
+    let (fee_amt, refund_amt) = get_unwrap_fee_and_refund(deps.storage, &asset, amount_to_unwrap)?;
+    Ok(FeeFromUnwrapAmountResponse {
+        amount_to_unwrap: amount_to_unwrap.to_string(),
+        fee_amt: fee_amt.to_string(),
+        refund_amt: refund_amt.to_string(),
+    })
+}
+
+fn query_amount_to_wrap(
+    deps: Deps,
+    target_amount: String,
+    token: Option<String>,
+) -> StdResult<GetAmountToWrapResponse> {
     let config = CONFIG.load(deps.storage)?;
+    let asset = read_asset(deps, token)?;
+    let fee_percentage = read_fee_percentage(deps.storage, &config, &asset)?;
     let target_amount = Uint128::from_str(&target_amount)?;
-    let amount_to_wrap = get_amount_to_wrap(target_amount, config.fee_percentage);
+    // Scale the "target_amount" to the underlying asset decimals(rounding up)
+    let underlying_amount = to_underlying_amount(deps.storage, &asset, target_amount, true)?;
+    let amount_to_wrap = get_amount_to_wrap(underlying_amount, fee_percentage);
     Ok(GetAmountToWrapResponse {
         target_amount: target_amount.to_string(),
         amount_to_wrap: amount_to_wrap.to_string(),
     })
 }
+
+fn query_token_config(deps: Deps, token: Option<String>) -> StdResult<TokenConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let asset = read_asset(deps, token)?;
+    let fee_percentage = read_fee_percentage(deps.storage, &config, &asset)?;
+    let max_share = MAX_SHARES.may_load(deps.storage, asset.clone())?;
+    let decimals = read_decimals(deps.storage, &asset)?;
+    Ok(TokenConfigResponse {
+        asset,
+        decimals,
+        fee_percentage,
+        max_share,
+    })
+}
+
+fn query_trusted_callers(deps: Deps) -> StdResult<TrustedCallersResponse> {
+    let callers = TRUSTED_CALLERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| matches!(item, Ok((_, true)) | Err(_)))
+        .map(|item| item.map(|(caller, _)| caller.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+    Ok(TrustedCallersResponse { callers })
+}
+
+fn query_reserves(deps: Deps) -> StdResult<ReservesResponse> {
+    let reserves = RESERVES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(asset, amount)| Reserve { asset, amount }))
+        .collect::<StdResult<Vec<Reserve>>>()?;
+    Ok(ReservesResponse { reserves })
+}
+
+fn query_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    is_historical: bool,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?
+        .map(Bound::exclusive);
+    let map = if is_historical {
+        HISTORICAL_TOKENS
+    } else {
+        TOKENS
+    };
+    let tokens = map
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| matches!(item, Ok((_, true)) | Err(_)))
+        .take(limit)
+        .map(|item| item.map(|(token, _)| token.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+    Ok(TokensResponse { tokens })
+}
+
+fn query_native_denoms(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    is_historical: bool,
+) -> StdResult<NativeDenomsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let map = if is_historical {
+        HISTORICAL_NATIVE_TOKENS
+    } else {
+        NATIVE_TOKENS
+    };
+    let denoms = map
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| matches!(item, Ok((_, true)) | Err(_)))
+        .take(limit)
+        .map(|item| item.map(|(denom, _)| denom))
+        .collect::<StdResult<Vec<String>>>()?;
+    Ok(NativeDenomsResponse { denoms })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // The wrappers deployed before the "handler" have no "handler" in the config.
+    let config = migrate_config(deps.storage)?;
+
+    // The wrappers deployed before the native denom list only allow the default native token denom.
+    if !HISTORICAL_NATIVE_TOKENS.has(deps.storage, config.native_token_denom.clone()) {
+        NATIVE_TOKENS.save(deps.storage, config.native_token_denom.clone(), &true)?;
+        HISTORICAL_NATIVE_TOKENS.save(deps.storage, config.native_token_denom, &true)?;
+    }
+
+    // Seed the reserves from the underlying assets actually held by the wrapper,
+    // since the wrappers deployed before the reserve tracking have no reserves recorded.
+    // The assets already tracked are kept as is, so that the donated assets never become the reserves.
+    let denoms = HISTORICAL_NATIVE_TOKENS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok(denom) if RESERVES.has(deps.storage, denom.to_string())))
+        .collect::<StdResult<Vec<String>>>()?;
+    let tokens = HISTORICAL_TOKENS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok(token) if RESERVES.has(deps.storage, token.to_string())))
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    let mut seeded: Vec<String> = vec![];
+    for denom in denoms {
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, denom.clone())?;
+        if !balance.amount.is_zero() {
+            RESERVES.save(deps.storage, denom, &balance.amount)?;
+            seeded.push(balance.to_string());
+        }
+    }
+    for token in tokens {
+        let balance: BalanceResponse = deps.querier.query_wasm_smart(
+            token.to_string(),
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        if !balance.balance.is_zero() {
+            RESERVES.save(deps.storage, token.to_string(), &balance.balance)?;
+            seeded.push(format!("{}{}", balance.balance, token));
+        }
+    }
+
+    let mut res = Response::new().add_attribute("action", "migrate");
+    if !seeded.is_empty() {
+        res = res.add_attribute("reserves", seeded.join(","));
+    }
+    Ok(res)
+}
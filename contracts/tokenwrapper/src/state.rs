@@ -1,7 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw20_base::state::TOKEN_INFO;
 use cw_storage_plus::{Item, Map};
 
 /// Config
@@ -9,15 +10,115 @@ use cw_storage_plus::{Item, Map};
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Config {
     pub governor: Addr,
+    pub handler: Addr,
     pub native_token_denom: String,
     pub fee_recipient: Addr,
     pub fee_percentage: u16,
     pub is_native_allowed: bool,
     pub wrapping_limit: Uint128,
     pub proposal_nonce: u64,
+    #[serde(default)]
+    pub unwrap_fee_percentage: u16,
+    #[serde(default)]
+    pub is_unwrap_fee_retained: bool,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// Config of the wrappers deployed before the "handler"
+#[derive(Serialize, Deserialize)]
+struct LegacyConfig {
+    governor: Addr,
+    #[serde(default)]
+    handler: Option<Addr>,
+    native_token_denom: String,
+    fee_recipient: Addr,
+    fee_percentage: u16,
+    is_native_allowed: bool,
+    wrapping_limit: Uint128,
+    proposal_nonce: u64,
+    #[serde(default)]
+    unwrap_fee_percentage: u16,
+    #[serde(default)]
+    is_unwrap_fee_retained: bool,
+}
+
+const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+
+// Loads the config of any deployed wrapper & saves it in the current format.
+// The "handler" defaults to the "governor", if not set.
+pub fn migrate_config(store: &mut dyn Storage) -> StdResult<Config> {
+    let legacy = LEGACY_CONFIG.load(store)?;
+    let config = Config {
+        handler: legacy.handler.unwrap_or_else(|| legacy.governor.clone()),
+        governor: legacy.governor,
+        native_token_denom: legacy.native_token_denom,
+        fee_recipient: legacy.fee_recipient,
+        fee_percentage: legacy.fee_percentage,
+        is_native_allowed: legacy.is_native_allowed,
+        wrapping_limit: legacy.wrapping_limit,
+        proposal_nonce: legacy.proposal_nonce,
+        unwrap_fee_percentage: legacy.unwrap_fee_percentage,
+        is_unwrap_fee_retained: legacy.is_unwrap_fee_retained,
+    };
+    CONFIG.save(store, &config)?;
+    Ok(config)
+}
+
 pub const TOKENS: Map<Addr, bool> = Map::new("tokens");
 pub const HISTORICAL_TOKENS: Map<Addr, bool> = Map::new("historical_tokens");
+
+/// Native token denoms(including IBC denoms) allowed to wrap
+pub const NATIVE_TOKENS: Map<String, bool> = Map::new("native_tokens");
+pub const HISTORICAL_NATIVE_TOKENS: Map<String, bool> = Map::new("historical_native_tokens");
+
+/// Decimals of the underlying assets, recorded when added to wrapping list.
+/// Key is the native token denom or the cw20 token address.
+pub const TOKEN_DECIMALS: Map<String, u8> = Map::new("token_decimals");
+
+/// Wrapping fee percentage( 0 ~ 10,000 ) of the underlying assets,
+/// which overrides the `fee_percentage` of config.
+/// Key is the native token denom or the cw20 token address.
+pub const FEE_PERCENTAGES: Map<String, u16> = Map::new("fee_percentages");
+
+/// Max share( 0 ~ 10,000 ) of the underlying assets in the reserves, checked on wrapping.
+/// Key is the native token denom or the cw20 token address.
+pub const MAX_SHARES: Map<String, u16> = Map::new("max_shares");
+
+/// Contracts(eg: anchor, vanchor) allowed to wrap/unwrap on behalf of any account
+pub const TRUSTED_CALLERS: Map<Addr, bool> = Map::new("trusted_callers");
+
+/// Amount of the underlying assets held by the wrapper
+/// Key is the native token denom or the cw20 token address.
+pub const RESERVES: Map<String, Uint128> = Map::new("reserves");
+
+pub fn read_reserve(store: &dyn Storage, asset: &str) -> StdResult<Uint128> {
+    Ok(RESERVES
+        .may_load(store, asset.to_string())?
+        .unwrap_or_default())
+}
+
+pub fn increase_reserve(store: &mut dyn Storage, asset: &str, amount: Uint128) -> StdResult<()> {
+    let reserve = read_reserve(store, asset)?.checked_add(amount)?;
+    RESERVES.save(store, asset.to_string(), &reserve)
+}
+
+pub fn decrease_reserve(store: &mut dyn Storage, asset: &str, amount: Uint128) -> StdResult<()> {
+    let reserve = read_reserve(store, asset)?.checked_sub(amount)?;
+    RESERVES.save(store, asset.to_string(), &reserve)
+}
+
+pub fn read_fee_percentage(store: &dyn Storage, config: &Config, asset: &str) -> StdResult<u16> {
+    Ok(FEE_PERCENTAGES
+        .may_load(store, asset.to_string())?
+        .unwrap_or(config.fee_percentage))
+}
+
+// Decimals of the underlying asset.
+// Same as the wrapped token if not recorded(eg: default native token denom).
+pub fn read_decimals(store: &dyn Storage, asset: &str) -> StdResult<u8> {
+    match TOKEN_DECIMALS.may_load(store, asset.to_string())? {
+        Some(decimals) => Ok(decimals),
+        None => Ok(TOKEN_INFO.load(store)?.decimals),
+    }
+}
@@ -2,15 +2,23 @@ use cosmwasm_std::testing::{
     mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier,
     MockStorage,
 };
-use cosmwasm_std::{attr, coins, from_binary, to_binary, Addr, Coin, OwnedDeps, Uint128};
-use cw20::{BalanceResponse, Cw20ReceiveMsg, TokenInfoResponse};
+use cosmwasm_std::{
+    attr, coins, from_binary, to_binary, Addr, BankMsg, Coin, ContractResult, CosmosMsg, OwnedDeps,
+    SubMsg, SystemError, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::{
+    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse, Cw20ExecuteMsg,
+    Cw20ReceiveMsg, TokenInfoResponse,
+};
 
+use protocol_cosmwasm::error::ContractError;
 use protocol_cosmwasm::token_wrapper::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, FeeFromAmountResponse, GetAmountToWrapResponse,
-    InstantiateMsg, QueryMsg,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, FeeFromAmountResponse, FeeFromUnwrapAmountResponse,
+    GetAmountToWrapResponse, InstantiateMsg, MigrateMsg, NativeDenomsResponse, QueryMsg, Reserve,
+    ReservesResponse, TokenConfigResponse, TokensResponse, TrustedCallersResponse,
 };
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, migrate, query};
 
 const NAME: &str = "Webb-WRAP";
 const SYMBOL: &str = "WWRP";
@@ -22,8 +30,41 @@ const CW20_TOKEN: &str = "cw20_token";
 const WRAPPING_LIMIT: u128 = 5000000;
 const IS_NATIVE_ALLOWED: bool = true;
 
+// Mocks the "TokenInfo" query of cw20 tokens.
+// The tokens not in "token_decimals" have the same decimals as the wrapped token.
+fn mock_token_decimals(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    token_decimals: &[(&str, u8)],
+) {
+    let token_decimals: Vec<(String, u8)> = token_decimals
+        .iter()
+        .map(|(token, decimals)| (token.to_string(), *decimals))
+        .collect();
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, .. } => {
+            let decimals = token_decimals
+                .iter()
+                .find(|(token, _)| token == contract_addr)
+                .map_or(DECIMALS, |(_, decimals)| *decimals);
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&TokenInfoResponse {
+                    name: contract_addr.to_string(),
+                    symbol: "TKN".to_string(),
+                    decimals,
+                    total_supply: Uint128::zero(),
+                })
+                .unwrap(),
+            ))
+        }
+        _ => SystemResult::Err(SystemError::UnsupportedRequest {
+            kind: "wasm".to_string(),
+        }),
+    });
+}
+
 fn init_tokenwrapper(coins: Vec<Coin>) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
     let mut deps = mock_dependencies_with_balance(&coins);
+    mock_token_decimals(&mut deps, &[]);
 
     let info = mock_info("creator", &[]);
     let instantiate_msg = InstantiateMsg {
@@ -31,6 +72,7 @@ fn init_tokenwrapper(coins: Vec<Coin>) -> OwnedDeps<MockStorage, MockApi, MockQu
         symbol: SYMBOL.to_string(),
         decimals: DECIMALS,
         governor: None,
+        handler: None,
         fee_recipient: FEE_RECIPIENT.to_string(),
         fee_percentage: FEE_PERCENTAGE,
         native_token_denom: NATIVE_TOKEN_DENOM.to_string(),
@@ -53,6 +95,7 @@ fn proper_initialization() {
         symbol: SYMBOL.to_string(),
         decimals: DECIMALS,
         governor: None,
+        handler: None,
         fee_recipient: FEE_RECIPIENT.to_string(),
         fee_percentage: FEE_PERCENTAGE,
         native_token_denom: NATIVE_TOKEN_DENOM.to_string(),
@@ -77,6 +120,7 @@ fn proper_initialization() {
     let config_response: ConfigResponse = from_binary(&query_bin).unwrap();
 
     assert_eq!(config_response.governor, "creator".to_string());
+    assert_eq!(config_response.handler, "creator".to_string());
     assert_eq!(
         config_response.native_token_denom,
         NATIVE_TOKEN_DENOM.to_string()
@@ -141,6 +185,7 @@ fn test_unwrap_native() {
     let info = mock_info("anyone", &[]);
     let unwrap_msg = ExecuteMsg::Unwrap {
         token: None,
+        denom: None,
         amount: Uint128::from(8000_u128),
         sender: None,
         recipient: None,
@@ -156,6 +201,7 @@ fn test_unwrap_native() {
             attr("to", "anyone"),
             attr("unwrap", "8000"),
             attr("refund", "8000"),
+            attr("fee", "0"),
         ]
     );
 
@@ -251,6 +297,7 @@ fn test_unwrap_cw20() {
     let info = mock_info("anyone", &[]);
     let unwrap_msg = ExecuteMsg::Unwrap {
         token: Some(Addr::unchecked(CW20_TOKEN.to_string())),
+        denom: None,
         amount: Uint128::from(8000_u128),
         sender: None,
         recipient: None,
@@ -266,6 +313,7 @@ fn test_unwrap_cw20() {
             attr("to", "anyone"),
             attr("unwrap", "8000"),
             attr("refund", "8000"),
+            attr("fee", "0"),
         ]
     );
 
@@ -292,6 +340,7 @@ fn test_query_fee_from_wrap_amt() {
         mock_env(),
         QueryMsg::FeeFromAmount {
             amount_to_wrap: "10000".to_string(),
+            token: None,
         },
     )
     .unwrap();
@@ -310,6 +359,7 @@ fn test_query_amt_to_wrap_from_target_amount() {
         mock_env(),
         QueryMsg::GetAmountToWrap {
             target_amount: "10000".to_string(),
+            token: None,
         },
     )
     .unwrap();
@@ -342,6 +392,76 @@ fn test_update_config() {
     assert_eq!(config_response.governor, "new_governor".to_string());
 }
 
+#[test]
+fn test_set_handler() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Fails to "set handler" if tx sender is neither the handler nor the governor
+    let info = mock_info("anyone", &[]);
+    let set_handler_msg = ExecuteMsg::SetHandler {
+        handler: "tokenwrapper-handler".to_string(),
+        nonce: 1,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, set_handler_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fails to "set handler" if "nonce" is too big or small
+    let info = mock_info("creator", &[]);
+    let set_handler_msg = ExecuteMsg::SetHandler {
+        handler: "tokenwrapper-handler".to_string(),
+        nonce: 2000,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, set_handler_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidNonce);
+
+    // Succeed to "set handler"
+    let info = mock_info("creator", &[]);
+    let set_handler_msg = ExecuteMsg::SetHandler {
+        handler: "tokenwrapper-handler".to_string(),
+        nonce: 1,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, set_handler_msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "set_handler"),
+            attr("handler", "tokenwrapper-handler"),
+            attr("nonce", "1"),
+        ]
+    );
+
+    // The governor can no longer execute the proposals
+    let info = mock_info("creator", &[]);
+    let configure_msg = ExecuteMsg::ConfigureFeePercentage {
+        fee_percentage: Some(10),
+        nonce: 2,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, configure_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The handler executes the proposals
+    let info = mock_info("tokenwrapper-handler", &[]);
+    let configure_msg = ExecuteMsg::ConfigureFeePercentage {
+        fee_percentage: Some(10),
+        nonce: 2,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_msg).unwrap();
+
+    let query_bin = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(config_response.handler, "tokenwrapper-handler".to_string());
+    assert_eq!(config_response.fee_percentage, "10".to_string());
+    assert_eq!(config_response.proposal_nonce, "2".to_string());
+
+    // The governor can replace the handler in emergency
+    let info = mock_info("creator", &[]);
+    let set_handler_msg = ExecuteMsg::SetHandler {
+        handler: "new-handler".to_string(),
+        nonce: 3,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, set_handler_msg).unwrap();
+}
+
 #[test]
 fn test_add_token_addr() {
     let mut deps = init_tokenwrapper([].to_vec());
@@ -403,3 +523,1652 @@ fn test_remove_token_addr() {
         ]
     )
 }
+
+#[test]
+fn test_query_tokens() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Add the cw20 tokens & remove one of them
+    for (nonce, token) in ["cw20_token_a", "cw20_token_b", "cw20_token_c"]
+        .iter()
+        .enumerate()
+    {
+        let info = mock_info("creator", &[]);
+        let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+            token: token.to_string(),
+            nonce: nonce as u64 + 1,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+    }
+    let info = mock_info("creator", &[]);
+    let remove_token_msg = ExecuteMsg::RemoveCw20TokenAddr {
+        token: "cw20_token_b".to_string(),
+        nonce: 4,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, remove_token_msg).unwrap();
+
+    // Removed token is not wrappable, but still unwrappable
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Tokens {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let tokens: TokensResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(
+        tokens.tokens,
+        vec!["cw20_token_a".to_string(), "cw20_token_c".to_string()]
+    );
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::HistoricalTokens {
+            start_after: Some("cw20_token_a".to_string()),
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    let tokens: TokensResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(tokens.tokens, vec!["cw20_token_b".to_string()]);
+
+    // Wrap the native token & approve the spender
+    let info = mock_info("owner", &coins(10000, "uusd"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    let info = mock_info("owner", &[]);
+    let allowance_msg = ExecuteMsg::IncreaseAllowance {
+        spender: "spender".to_string(),
+        amount: Uint128::from(1000_u128),
+        expires: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, allowance_msg).unwrap();
+
+    // Check the cw20 "enumerable" queries
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AllAccounts {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let accounts: AllAccountsResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(accounts.accounts, vec!["owner".to_string()]);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AllAllowances {
+            owner: "owner".to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let allowances: AllAllowancesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(allowances.allowances.len(), 1);
+    assert_eq!(allowances.allowances[0].spender, "spender".to_string());
+    assert_eq!(allowances.allowances[0].allowance, Uint128::from(1000_u128));
+}
+
+#[test]
+fn test_unwrap_on_behalf_of() {
+    let ctx_coins = coins(100_u128, "uusd");
+    let mut deps = init_tokenwrapper(ctx_coins);
+
+    // Wrap the native token for "owner"
+    let info = mock_info("owner", &coins(10000, "uusd"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    // Fails to unwrap the tokens of "owner" without the allowance
+    let info = mock_info("attacker", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: None,
+        denom: None,
+        amount: Uint128::from(1000_u128),
+        sender: Some("owner".to_string()),
+        recipient: Some("attacker".to_string()),
+    };
+    assert!(execute(deps.as_mut(), mock_env(), info, unwrap_msg).is_err());
+
+    // Succeed to unwrap the tokens of "owner" by consuming the allowance
+    let info = mock_info("owner", &[]);
+    let allowance_msg = ExecuteMsg::IncreaseAllowance {
+        spender: "spender".to_string(),
+        amount: Uint128::from(1000_u128),
+        expires: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, allowance_msg).unwrap();
+
+    let info = mock_info("spender", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: None,
+        denom: None,
+        amount: Uint128::from(1000_u128),
+        sender: Some("owner".to_string()),
+        recipient: Some("spender".to_string()),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), unwrap_msg.clone()).unwrap();
+
+    // The allowance is consumed
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Allowance {
+            owner: "owner".to_string(),
+            spender: "spender".to_string(),
+        },
+    )
+    .unwrap();
+    let allowance: AllowanceResponse = from_binary(&res).unwrap();
+    assert_eq!(allowance.allowance, Uint128::zero());
+    assert!(execute(deps.as_mut(), mock_env(), info, unwrap_msg).is_err());
+
+    // Fails to configure the trusted caller if tx sender is not governor
+    let info = mock_info("anyone", &[]);
+    let configure_msg = ExecuteMsg::ConfigureTrustedCaller {
+        caller: "anchor".to_string(),
+        is_trusted: true,
+        nonce: 1,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, configure_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Trusted caller can unwrap on behalf of "owner" without the allowance
+    let info = mock_info("creator", &[]);
+    let configure_msg = ExecuteMsg::ConfigureTrustedCaller {
+        caller: "anchor".to_string(),
+        is_trusted: true,
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::TrustedCallers {}).unwrap();
+    let trusted_callers: TrustedCallersResponse = from_binary(&res).unwrap();
+    assert_eq!(trusted_callers.callers, vec!["anchor".to_string()]);
+
+    let info = mock_info("anchor", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: None,
+        denom: None,
+        amount: Uint128::from(1000_u128),
+        sender: Some("owner".to_string()),
+        recipient: Some("owner".to_string()),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+
+    // Check the token amounts
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: "owner".to_string(),
+        },
+    )
+    .unwrap();
+    let token_balance: BalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(token_balance.balance.u128(), 7999);
+
+    // Fails to wrap & send the tokens of "owner" without the allowance
+    let info = mock_info("attacker", &coins(1, "uusd"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: Some("owner".to_string()),
+        recipient: Some("attacker".to_string()),
+    };
+    assert!(execute(deps.as_mut(), mock_env(), info, wrap_msg).is_err());
+}
+
+#[test]
+fn test_reserves() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Add a cw20 address to wrapping list.
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    // Wrap the native & cw20 tokens
+    let info = mock_info("anyone", &coins(10000, "uusd"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    let info = mock_info(CW20_TOKEN, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "anyone".to_string(),
+        amount: Uint128::from(5000_u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {
+            sender: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    // Check the reserves(excluding the wrapping fee)
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        reserves.reserves,
+        vec![
+            Reserve {
+                asset: CW20_TOKEN.to_string(),
+                amount: Uint128::from(5000_u128),
+            },
+            Reserve {
+                asset: NATIVE_TOKEN_DENOM.to_string(),
+                amount: Uint128::from(9999_u128),
+            },
+        ]
+    );
+
+    // Fails to unwrap more cw20 tokens than the reserve
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: Some(Addr::unchecked(CW20_TOKEN.to_string())),
+        denom: None,
+        amount: Uint128::from(6000_u128),
+        sender: None,
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientReserve);
+
+    // Succeed to unwrap within the reserve
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: Some(Addr::unchecked(CW20_TOKEN.to_string())),
+        denom: None,
+        amount: Uint128::from(5000_u128),
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(reserves.reserves[0].amount, Uint128::zero());
+    assert_eq!(reserves.reserves[1].amount, Uint128::from(9999_u128));
+}
+
+#[test]
+fn test_token_fee_and_wrapping_limit() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Add a cw20 address to wrapping list.
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    // Fails to configure the token fee if tx sender is not governor
+    let info = mock_info("anyone", &[]);
+    let configure_fee_msg = ExecuteMsg::ConfigureTokenFeePercentage {
+        token: Some(CW20_TOKEN.to_string()),
+        fee_percentage: Some(100),
+        nonce: 2,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, configure_fee_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fails to configure the token fee with used nonce
+    let info = mock_info("creator", &[]);
+    let configure_fee_msg = ExecuteMsg::ConfigureTokenFeePercentage {
+        token: Some(CW20_TOKEN.to_string()),
+        fee_percentage: Some(100),
+        nonce: 1,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, configure_fee_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidNonce);
+
+    // Succeed to configure the fee of cw20 token(1%) & limit the native token to 50% of reserves
+    let info = mock_info("creator", &[]);
+    let configure_fee_msg = ExecuteMsg::ConfigureTokenFeePercentage {
+        token: Some(CW20_TOKEN.to_string()),
+        fee_percentage: Some(100),
+        nonce: 2,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_fee_msg).unwrap();
+
+    let info = mock_info("creator", &[]);
+    let configure_limit_msg = ExecuteMsg::ConfigureTokenWrappingLimit {
+        token: None,
+        max_share: Some(5000),
+        nonce: 3,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_limit_msg).unwrap();
+
+    // Check the token configs
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::TokenConfig {
+            token: Some(CW20_TOKEN.to_string()),
+        },
+    )
+    .unwrap();
+    let token_config: TokenConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        token_config,
+        TokenConfigResponse {
+            asset: CW20_TOKEN.to_string(),
+            decimals: DECIMALS,
+            fee_percentage: 100,
+            max_share: None,
+        }
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::TokenConfig { token: None },
+    )
+    .unwrap();
+    let token_config: TokenConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        token_config,
+        TokenConfigResponse {
+            asset: NATIVE_TOKEN_DENOM.to_string(),
+            decimals: DECIMALS,
+            fee_percentage: FEE_PERCENTAGE,
+            max_share: Some(5000),
+        }
+    );
+
+    // Check the fee queries of cw20 token
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::FeeFromAmount {
+            amount_to_wrap: "10000".to_string(),
+            token: Some(CW20_TOKEN.to_string()),
+        },
+    )
+    .unwrap();
+    let fee_response: FeeFromAmountResponse = from_binary(&res).unwrap();
+    assert_eq!(fee_response.fee_amt, "100".to_string());
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::GetAmountToWrap {
+            target_amount: "9900".to_string(),
+            token: Some(CW20_TOKEN.to_string()),
+        },
+    )
+    .unwrap();
+    let amt_response: GetAmountToWrapResponse = from_binary(&res).unwrap();
+    assert_eq!(amt_response.amount_to_wrap, "10000".to_string());
+
+    // Wrap the cw20 tokens with its own fee
+    let info = mock_info(CW20_TOKEN, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "anyone".to_string(),
+        amount: Uint128::from(10000_u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {
+            sender: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(res.attributes[4], attr("minted", "9900"));
+    assert_eq!(res.attributes[5], attr("fee", "100"));
+
+    // Fails to wrap the native token over 50% of reserves
+    let info = mock_info("anyone", &coins(10000, "uusd"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap_err();
+    assert_eq!(err, ContractError::MaxShareExceeded);
+
+    // Succeed to wrap the native token within 50% of reserves
+    let info = mock_info("anyone", &coins(9900, "uusd"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(reserves.reserves[0].amount, Uint128::from(9900_u128));
+    assert_eq!(reserves.reserves[1].amount, Uint128::from(9900_u128));
+
+    // Remove the limit of native token
+    let info = mock_info("creator", &[]);
+    let configure_limit_msg = ExecuteMsg::ConfigureTokenWrappingLimit {
+        token: None,
+        max_share: None,
+        nonce: 4,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_limit_msg).unwrap();
+
+    let info = mock_info("anyone", &coins(10000, "uusd"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+}
+
+#[test]
+fn test_wrap_and_unwrap_with_token_decimals() {
+    const CW20_TOKEN_18: &str = "cw20_token_18";
+    const CW20_TOKEN_2: &str = "cw20_token_2";
+
+    let mut deps = init_tokenwrapper([].to_vec());
+    mock_token_decimals(&mut deps, &[(CW20_TOKEN_18, 18), (CW20_TOKEN_2, 2)]);
+
+    // Add the cw20 tokens with 18 & 2 decimals to wrapping list
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN_18.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN_2.to_string(),
+        nonce: 2,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::TokenConfig {
+            token: Some(CW20_TOKEN_18.to_string()),
+        },
+    )
+    .unwrap();
+    let token_config: TokenConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(token_config.decimals, 18);
+
+    // Wrap the 18 decimals token: the minted amount is rounded down
+    // 10^15 - fee(10^11) = 999_900_000_000_000 => 999.9 => 999
+    let info = mock_info(CW20_TOKEN_18, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "anyone".to_string(),
+        amount: Uint128::from(1_000_000_000_000_000_u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {
+            sender: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(res.attributes[4], attr("minted", "999"));
+    assert_eq!(res.attributes[5], attr("fee", "100000000000"));
+
+    // Fails to wrap the 18 decimals token, which mints nothing
+    let info = mock_info(CW20_TOKEN_18, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "anyone".to_string(),
+        amount: Uint128::from(999_999_999_999_u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {
+            sender: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let err = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Invalid cw20 token");
+
+    // Wrap the 2 decimals token: the minted amount is scaled up
+    let info = mock_info(CW20_TOKEN_2, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "anyone".to_string(),
+        amount: Uint128::from(100_u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {
+            sender: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(res.attributes[4], attr("minted", "1000000"));
+    assert_eq!(res.attributes[5], attr("fee", "0"));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: "anyone".to_string(),
+        },
+    )
+    .unwrap();
+    let token_balance: BalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(token_balance.balance, Uint128::from(1_000_999_u128));
+
+    // Unwrap into the 18 decimals token: the refund is scaled up
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: Some(Addr::unchecked(CW20_TOKEN_18.to_string())),
+        denom: None,
+        amount: Uint128::from(500_u128),
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(res.attributes[4], attr("unwrap", "500"));
+    assert_eq!(res.attributes[5], attr("refund", "500000000000000"));
+
+    // Unwrap into the 2 decimals token: the refund is rounded down
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: Some(Addr::unchecked(CW20_TOKEN_2.to_string())),
+        denom: None,
+        amount: Uint128::from(12_345_u128),
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(res.attributes[4], attr("unwrap", "12345"));
+    assert_eq!(res.attributes[5], attr("refund", "1"));
+
+    // Fails to unwrap into the 2 decimals token, which refunds nothing
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: Some(Addr::unchecked(CW20_TOKEN_2.to_string())),
+        denom: None,
+        amount: Uint128::from(9_999_u128),
+        sender: None,
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Too small unwrap amount for the cw20 token decimals"
+    );
+
+    // Check the reserves, in the underlying token decimals
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        reserves.reserves,
+        vec![
+            Reserve {
+                asset: CW20_TOKEN_18.to_string(),
+                amount: Uint128::from(499_900_000_000_000_u128),
+            },
+            Reserve {
+                asset: CW20_TOKEN_2.to_string(),
+                amount: Uint128::from(99_u128),
+            },
+        ]
+    );
+
+    // The amount to wrap is rounded up to the 2 decimals token
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::GetAmountToWrap {
+            target_amount: "12345".to_string(),
+            token: Some(CW20_TOKEN_2.to_string()),
+        },
+    )
+    .unwrap();
+    let amt_response: GetAmountToWrapResponse = from_binary(&res).unwrap();
+    assert_eq!(amt_response.amount_to_wrap, "2".to_string());
+}
+
+#[test]
+fn test_wrap_and_unwrap_multiple_native_denoms() {
+    const IBC_DENOM: &str = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Fails to add the native denom if tx sender is not governor
+    let info = mock_info("anyone", &[]);
+    let add_denom_msg = ExecuteMsg::AddNativeTokenDenom {
+        denom: IBC_DENOM.to_string(),
+        decimals: DECIMALS,
+        nonce: 1,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, add_denom_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fails to add the already valid native denom
+    let info = mock_info("creator", &[]);
+    let add_denom_msg = ExecuteMsg::AddNativeTokenDenom {
+        denom: NATIVE_TOKEN_DENOM.to_string(),
+        decimals: DECIMALS,
+        nonce: 1,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, add_denom_msg).unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Denom must not be valid");
+
+    // Succeed to add the IBC denom
+    let info = mock_info("creator", &[]);
+    let add_denom_msg = ExecuteMsg::AddNativeTokenDenom {
+        denom: IBC_DENOM.to_string(),
+        decimals: DECIMALS,
+        nonce: 1,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, add_denom_msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("method", "add_native_denom"),
+            attr("denom", IBC_DENOM),
+            attr("decimals", DECIMALS.to_string()),
+        ]
+    );
+
+    // Fails to wrap the denom not in wrapping list
+    let info = mock_info("anyone", &coins(10000, "uluna"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Invalid native token denom(uluna)"
+    );
+
+    // Wrap the multiple denoms in single message
+    let info = mock_info(
+        "anyone",
+        &[
+            Coin::new(10000, NATIVE_TOKEN_DENOM),
+            Coin::new(20000, IBC_DENOM),
+        ],
+    );
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(res.attributes[4], attr("minted", "29997"));
+    assert_eq!(res.attributes[5], attr("fee", "3"));
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: FEE_RECIPIENT.to_string(),
+            amount: vec![Coin::new(1, NATIVE_TOKEN_DENOM), Coin::new(2, IBC_DENOM)],
+        })
+    );
+
+    // Fails to unwrap with both of token & denom
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: Some(Addr::unchecked(CW20_TOKEN.to_string())),
+        denom: Some(IBC_DENOM.to_string()),
+        amount: Uint128::from(100_u128),
+        sender: None,
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Only one of token & denom can be given"
+    );
+
+    // Remove the IBC denom from wrapping list
+    let info = mock_info("creator", &[]);
+    let remove_denom_msg = ExecuteMsg::RemoveNativeTokenDenom {
+        denom: IBC_DENOM.to_string(),
+        nonce: 2,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, remove_denom_msg).unwrap();
+
+    let info = mock_info("anyone", &coins(10000, IBC_DENOM));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Generic error: Invalid native token denom({})", IBC_DENOM)
+    );
+
+    // Fails to remove the default native token denom
+    let info = mock_info("creator", &[]);
+    let remove_denom_msg = ExecuteMsg::RemoveNativeTokenDenom {
+        denom: NATIVE_TOKEN_DENOM.to_string(),
+        nonce: 3,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, remove_denom_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Default native token denom cannot be removed"
+    );
+
+    // Removed denom is not wrappable, but still unwrappable
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::NativeDenoms {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let denoms: NativeDenomsResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(denoms.denoms, vec![NATIVE_TOKEN_DENOM.to_string()]);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::HistoricalNativeDenoms {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let denoms: NativeDenomsResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(
+        denoms.denoms,
+        vec![IBC_DENOM.to_string(), NATIVE_TOKEN_DENOM.to_string()]
+    );
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::HistoricalNativeDenoms {
+            start_after: Some(IBC_DENOM.to_string()),
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    let denoms: NativeDenomsResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(denoms.denoms, vec![NATIVE_TOKEN_DENOM.to_string()]);
+
+    // Still can unwrap into the removed denom
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: None,
+        denom: Some(IBC_DENOM.to_string()),
+        amount: Uint128::from(15000_u128),
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "anyone".to_string(),
+            amount: coins(15000, IBC_DENOM),
+        })
+    );
+
+    // Check the reserves
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        reserves.reserves,
+        vec![
+            Reserve {
+                asset: IBC_DENOM.to_string(),
+                amount: Uint128::from(4998_u128),
+            },
+            Reserve {
+                asset: NATIVE_TOKEN_DENOM.to_string(),
+                amount: Uint128::from(9999_u128),
+            },
+        ]
+    );
+}
+
+#[test]
+#[test]
+fn test_wrap_and_unwrap_native_denom_with_decimals() {
+    const DENOM_18: &str = "aevmos";
+
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Add the native denom of 18 decimals
+    let info = mock_info("creator", &[]);
+    let add_denom_msg = ExecuteMsg::AddNativeTokenDenom {
+        denom: DENOM_18.to_string(),
+        decimals: 18,
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_denom_msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::TokenConfig {
+            token: Some(DENOM_18.to_string()),
+        },
+    )
+    .unwrap();
+    let token_config: TokenConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(token_config.decimals, 18);
+
+    // Limit the share of the denom in the reserves
+    let info = mock_info("creator", &[]);
+    let configure_limit_msg = ExecuteMsg::ConfigureTokenWrappingLimit {
+        token: Some(DENOM_18.to_string()),
+        max_share: Some(5000),
+        nonce: 2,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_limit_msg).unwrap();
+
+    let info = mock_info("anyone", &coins(10000, NATIVE_TOKEN_DENOM));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    // Fails to wrap the amount less than a unit of wrapped token
+    let info = mock_info("anyone", &coins(100_000_000_000, DENOM_18));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Invalid native token amount"
+    );
+
+    // Fails to wrap over the max share, compared in the wrapped token decimals
+    let info = mock_info("anyone", &coins(11_000_000_000_000_000, DENOM_18));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap_err();
+    assert_eq!(err, ContractError::MaxShareExceeded);
+
+    // Wrap the denom, minting the amount scaled to the wrapped token decimals
+    let info = mock_info("anyone", &coins(5_000_000_000_000_000, DENOM_18));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(res.attributes[4], attr("minted", "4999"));
+    assert_eq!(res.attributes[5], attr("fee", "500000000000"));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: "anyone".to_string(),
+        },
+    )
+    .unwrap();
+    let balance: BalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(balance.balance, Uint128::from(14998_u128));
+
+    // Unwrap into the denom, scaled to the denom decimals
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: None,
+        denom: Some(DENOM_18.to_string()),
+        amount: Uint128::from(4999_u128),
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "anyone".to_string(),
+            amount: coins(4_999_000_000_000_000, DENOM_18),
+        }))]
+    );
+
+    // The rounding dust is kept in the reserves
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        reserves.reserves,
+        vec![
+            Reserve {
+                asset: DENOM_18.to_string(),
+                amount: Uint128::from(500_000_000_000_u128),
+            },
+            Reserve {
+                asset: NATIVE_TOKEN_DENOM.to_string(),
+                amount: Uint128::from(9999_u128),
+            },
+        ]
+    );
+
+    // Fails to re-add the denom with the different decimals
+    let info = mock_info("creator", &[]);
+    let remove_denom_msg = ExecuteMsg::RemoveNativeTokenDenom {
+        denom: DENOM_18.to_string(),
+        nonce: 3,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, remove_denom_msg).unwrap();
+
+    let info = mock_info("creator", &[]);
+    let add_denom_msg = ExecuteMsg::AddNativeTokenDenom {
+        denom: DENOM_18.to_string(),
+        decimals: 6,
+        nonce: 4,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, add_denom_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Decimals must be same as the recorded decimals of denom"
+    );
+
+    let info = mock_info("creator", &[]);
+    let add_denom_msg = ExecuteMsg::AddNativeTokenDenom {
+        denom: DENOM_18.to_string(),
+        decimals: 18,
+        nonce: 4,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_denom_msg).unwrap();
+}
+
+fn test_unwrap_fee() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Wrap the native token
+    let info = mock_info("anyone", &coins(10000, "uusd"));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    // Fails to configure the unwrap fee if tx sender is not governor
+    let info = mock_info("anyone", &[]);
+    let configure_fee_msg = ExecuteMsg::ConfigureUnwrapFee {
+        unwrap_fee_percentage: 100,
+        is_unwrap_fee_retained: false,
+        nonce: 1,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, configure_fee_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Set the unwrap fee(1%), sent to "fee_recipient"
+    let info = mock_info("creator", &[]);
+    let configure_fee_msg = ExecuteMsg::ConfigureUnwrapFee {
+        unwrap_fee_percentage: 100,
+        is_unwrap_fee_retained: false,
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_fee_msg).unwrap();
+
+    let query_bin = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(config_response.unwrap_fee_percentage, "100".to_string());
+    assert_eq!(config_response.is_unwrap_fee_retained, "false".to_string());
+
+    // Check the query "FeeFromUnwrapAmount"
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::FeeFromUnwrapAmount {
+            amount_to_unwrap: "5000".to_string(),
+            token: None,
+        },
+    )
+    .unwrap();
+    let fee_response: FeeFromUnwrapAmountResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(
+        fee_response,
+        FeeFromUnwrapAmountResponse {
+            amount_to_unwrap: "5000".to_string(),
+            fee_amt: "50".to_string(),
+            refund_amt: "4950".to_string(),
+        }
+    );
+
+    // Unwrap the native token, paying the unwrap fee to "fee_recipient"
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: None,
+        denom: None,
+        amount: Uint128::from(5000_u128),
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(res.attributes[5], attr("refund", "4950"));
+    assert_eq!(res.attributes[6], attr("fee", "50"));
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<CosmosMsg>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "anyone".to_string(),
+                amount: coins(4950, "uusd"),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: FEE_RECIPIENT.to_string(),
+                amount: coins(50, "uusd"),
+            }),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(reserves.reserves[0].amount, Uint128::from(4999_u128));
+
+    // Retain the unwrap fee in the reserves
+    let info = mock_info("creator", &[]);
+    let configure_fee_msg = ExecuteMsg::ConfigureUnwrapFee {
+        unwrap_fee_percentage: 100,
+        is_unwrap_fee_retained: true,
+        nonce: 2,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_fee_msg).unwrap();
+
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: None,
+        denom: None,
+        amount: Uint128::from(1000_u128),
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<CosmosMsg>>(),
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "anyone".to_string(),
+            amount: coins(990, "uusd"),
+        })]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(reserves.reserves[0].amount, Uint128::from(4009_u128));
+}
+
+#[test]
+fn test_unwrap_pro_rata() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Add a cw20 address to wrapping list.
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    // "alice" wraps the native token & "bob" wraps the cw20 token
+    let info = mock_info("alice", &coins(10000, NATIVE_TOKEN_DENOM));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    let info = mock_info(CW20_TOKEN, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "bob".to_string(),
+        amount: Uint128::from(10000_u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {
+            sender: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    // Fails to unwrap more than the balance
+    let info = mock_info("alice", &[]);
+    let unwrap_msg = ExecuteMsg::UnwrapProRata {
+        amount: Uint128::from(10000_u128),
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Insufficient token balance for sender(alice)"
+    );
+
+    // Fails to unwrap the amount, which pays out nothing
+    let info = mock_info("alice", &[]);
+    let unwrap_msg = ExecuteMsg::UnwrapProRata {
+        amount: Uint128::from(1_u128),
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Too small unwrap amount for the reserves"
+    );
+
+    // "alice" unwraps into the basket of native & cw20 tokens
+    let info = mock_info("alice", &[]);
+    let unwrap_msg = ExecuteMsg::UnwrapProRata {
+        amount: Uint128::from(9999_u128),
+        recipient: Some("recipient".to_string()),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "unwrap_pro_rata"),
+            attr("from", "alice"),
+            attr("to", "recipient"),
+            attr("unwrap", "9999"),
+            attr("payouts", "4999cw20_token,4999uusd"),
+            attr("fees", "0cw20_token,0uusd"),
+        ]
+    );
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(4999, NATIVE_TOKEN_DENOM),
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: CW20_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "recipient".to_string(),
+                amount: Uint128::from(4999_u128),
+            })
+            .unwrap(),
+        })
+    );
+
+    // The rounding dust is retained in the reserves
+    let query_bin = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(
+        reserves.reserves,
+        vec![
+            Reserve {
+                asset: CW20_TOKEN.to_string(),
+                amount: Uint128::from(5000_u128),
+            },
+            Reserve {
+                asset: NATIVE_TOKEN_DENOM.to_string(),
+                amount: Uint128::from(5000_u128),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_unwrap_pro_rata_with_unwrap_fee() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Add a cw20 address to wrapping list.
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    // "alice" wraps the native token & "bob" wraps the cw20 token
+    let info = mock_info("alice", &coins(10000, NATIVE_TOKEN_DENOM));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    let info = mock_info(CW20_TOKEN, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "bob".to_string(),
+        amount: Uint128::from(10000_u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {
+            sender: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    // Charge the unwrap fee, sent to "fee_recipient"
+    let info = mock_info("creator", &[]);
+    let configure_fee_msg = ExecuteMsg::ConfigureUnwrapFee {
+        unwrap_fee_percentage: 100,
+        is_unwrap_fee_retained: false,
+        nonce: 2,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_fee_msg).unwrap();
+
+    let info = mock_info("alice", &[]);
+    let unwrap_msg = ExecuteMsg::UnwrapProRata {
+        amount: Uint128::from(9999_u128),
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "unwrap_pro_rata"),
+            attr("from", "alice"),
+            attr("to", "alice"),
+            attr("unwrap", "9999"),
+            attr("payouts", "4950cw20_token,4950uusd"),
+            attr("fees", "49cw20_token,49uusd"),
+        ]
+    );
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(4950, NATIVE_TOKEN_DENOM),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: FEE_RECIPIENT.to_string(),
+                amount: coins(49, NATIVE_TOKEN_DENOM),
+            })),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: CW20_TOKEN.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "alice".to_string(),
+                    amount: Uint128::from(4950_u128),
+                })
+                .unwrap(),
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: CW20_TOKEN.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: FEE_RECIPIENT.to_string(),
+                    amount: Uint128::from(49_u128),
+                })
+                .unwrap(),
+            }),
+        ]
+    );
+
+    let query_bin = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(reserves.reserves[0].amount, Uint128::from(5000_u128));
+    assert_eq!(reserves.reserves[1].amount, Uint128::from(5000_u128));
+
+    // Retain the unwrap fee in the reserves
+    let info = mock_info("creator", &[]);
+    let configure_fee_msg = ExecuteMsg::ConfigureUnwrapFee {
+        unwrap_fee_percentage: 100,
+        is_unwrap_fee_retained: true,
+        nonce: 3,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_fee_msg).unwrap();
+
+    let info = mock_info("bob", &[]);
+    let unwrap_msg = ExecuteMsg::UnwrapProRata {
+        amount: Uint128::from(4999_u128),
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(
+        res.attributes[4],
+        attr("payouts", "2475cw20_token,2475uusd")
+    );
+    assert_eq!(res.attributes[5], attr("fees", "24cw20_token,24uusd"));
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: coins(2475, NATIVE_TOKEN_DENOM),
+        })
+    );
+
+    let query_bin = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(reserves.reserves[0].amount, Uint128::from(2525_u128));
+    assert_eq!(reserves.reserves[1].amount, Uint128::from(2525_u128));
+}
+
+#[test]
+fn test_unwrap_pro_rata_native_not_allowed() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Add a cw20 address to wrapping list.
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    // Wrap the native & cw20 tokens
+    let info = mock_info("alice", &coins(10000, NATIVE_TOKEN_DENOM));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    let info = mock_info(CW20_TOKEN, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "alice".to_string(),
+        amount: Uint128::from(10000_u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {
+            sender: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+
+    // Disallow the native token
+    let info = mock_info("creator", &[]);
+    let configure_msg = ExecuteMsg::ConfigureNativeAllowed {
+        is_native_allowed: Some(false),
+        nonce: 2,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, configure_msg).unwrap();
+
+    // Fails to unwrap into the basket including the native token
+    let info = mock_info("alice", &[]);
+    let unwrap_msg = ExecuteMsg::UnwrapProRata {
+        amount: Uint128::from(10000_u128),
+        recipient: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Unwrapping native token is not allowed in this token wrapper"
+    );
+
+    // Nothing is burnt or released
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: "alice".to_string(),
+        },
+    )
+    .unwrap();
+    let balance: BalanceResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(balance.balance, Uint128::from(19998_u128));
+
+    let query_bin = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(reserves.reserves[0].amount, Uint128::from(9999_u128));
+    assert_eq!(reserves.reserves[1].amount, Uint128::from(9999_u128));
+}
+
+#[test]
+fn test_wrap_and_call() {
+    let mut deps = init_tokenwrapper([].to_vec());
+
+    // Add a cw20 address to wrapping list.
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    // Wrap the native token & send the wrapped tokens to "anchor" with the payload
+    let payload = to_binary("deposit").unwrap();
+    let info = mock_info("anyone", &coins(10000, NATIVE_TOKEN_DENOM));
+    let wrap_msg = ExecuteMsg::WrapAndCall {
+        contract: "anchor".to_string(),
+        msg: payload.clone(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "wrap_native"),
+            attr("from", "anyone"),
+            attr("owner", "anyone"),
+            attr("to", "anchor"),
+            attr("minted", "9999"),
+            attr("fee", "1"),
+        ]
+    );
+    let receive_msg: CosmosMsg = Cw20ReceiveMsg {
+        sender: "anyone".to_string(),
+        amount: Uint128::from(9999_u128),
+        msg: payload.clone(),
+    }
+    .into_cosmos_msg("anchor")
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.messages[1], SubMsg::new(receive_msg.clone()));
+
+    // Wrap the cw20 token & send the wrapped tokens to "anchor" with the payload
+    let info = mock_info(CW20_TOKEN, &[]);
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "anyone".to_string(),
+        amount: Uint128::from(10000_u128),
+        msg: to_binary(&Cw20HookMsg::WrapAndCall {
+            contract: "anchor".to_string(),
+            msg: payload,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(res.attributes[3], attr("to", "anchor"));
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.messages[1], SubMsg::new(receive_msg));
+
+    // The wrapped tokens are held by "anchor"
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: "anchor".to_string(),
+        },
+    )
+    .unwrap();
+    let token_balance: BalanceResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(token_balance.balance.u128(), 19998);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: "anyone".to_string(),
+        },
+    )
+    .unwrap();
+    let token_balance: BalanceResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(token_balance.balance, Uint128::zero());
+}
+
+#[test]
+fn test_migrate_seeds_reserves() {
+    use crate::state::{HISTORICAL_NATIVE_TOKENS, NATIVE_TOKENS, RESERVES};
+
+    let mut deps = init_tokenwrapper(coins(10000, NATIVE_TOKEN_DENOM));
+
+    // Add a cw20 address to wrapping list.
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    // Simulate the wrapper deployed before the reserve tracking & native denom list,
+    // which holds the underlying assets without any reserves recorded.
+    NATIVE_TOKENS.remove(&mut deps.storage, NATIVE_TOKEN_DENOM.to_string());
+    HISTORICAL_NATIVE_TOKENS.remove(&mut deps.storage, NATIVE_TOKEN_DENOM.to_string());
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == CW20_TOKEN => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&BalanceResponse {
+                    balance: Uint128::from(5000_u128),
+                })
+                .unwrap(),
+            ))
+        }
+        _ => SystemResult::Err(SystemError::UnsupportedRequest {
+            kind: "wasm".to_string(),
+        }),
+    });
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(reserves.reserves, vec![]);
+
+    // Migrate the wrapper
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate"),
+            attr("reserves", "10000uusd,5000cw20_token"),
+        ]
+    );
+
+    // Check the reserves are seeded from the balances
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        reserves.reserves,
+        vec![
+            Reserve {
+                asset: CW20_TOKEN.to_string(),
+                amount: Uint128::from(5000_u128),
+            },
+            Reserve {
+                asset: NATIVE_TOKEN_DENOM.to_string(),
+                amount: Uint128::from(10000_u128),
+            },
+        ]
+    );
+
+    // The seeded reserves are unwrappable after the migration
+    let info = mock_info("anyone", &coins(100, NATIVE_TOKEN_DENOM));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(
+        RESERVES
+            .load(&deps.storage, NATIVE_TOKEN_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(10099_u128)
+    );
+
+    let info = mock_info("anyone", &[]);
+    let unwrap_msg = ExecuteMsg::Unwrap {
+        token: Some(Addr::unchecked(CW20_TOKEN.to_string())),
+        denom: None,
+        amount: Uint128::from(99_u128),
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, unwrap_msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "unwrap_cw20"),
+            attr("from", "anyone"),
+            attr("owner", "anyone"),
+            attr("to", "anyone"),
+            attr("unwrap", "99"),
+            attr("refund", "99"),
+            attr("fee", "0"),
+        ]
+    );
+    assert_eq!(
+        RESERVES
+            .load(&deps.storage, CW20_TOKEN.to_string())
+            .unwrap(),
+        Uint128::from(4901_u128)
+    );
+
+    // Migrating again keeps the tracked reserves, regardless of the balances
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "migrate")]);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Reserves {}).unwrap();
+    let reserves: ReservesResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        reserves.reserves,
+        vec![
+            Reserve {
+                asset: CW20_TOKEN.to_string(),
+                amount: Uint128::from(4901_u128),
+            },
+            Reserve {
+                asset: NATIVE_TOKEN_DENOM.to_string(),
+                amount: Uint128::from(10099_u128),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_migrate_legacy_config() {
+    use crate::state::{HISTORICAL_NATIVE_TOKENS, NATIVE_TOKENS};
+    use cosmwasm_std::Storage;
+
+    let mut deps = init_tokenwrapper(coins(10000, NATIVE_TOKEN_DENOM));
+
+    // Simulate the wrapper deployed from the baseline, with the config without "handler".
+    let legacy_config = format!(
+        r#"{{"governor":"creator","native_token_denom":"{}","fee_recipient":"{}","fee_percentage":{},"is_native_allowed":true,"wrapping_limit":"{}","proposal_nonce":0}}"#,
+        NATIVE_TOKEN_DENOM, FEE_RECIPIENT, FEE_PERCENTAGE, WRAPPING_LIMIT
+    );
+    deps.storage.set(b"config", legacy_config.as_bytes());
+    NATIVE_TOKENS.remove(&mut deps.storage, NATIVE_TOKEN_DENOM.to_string());
+    HISTORICAL_NATIVE_TOKENS.remove(&mut deps.storage, NATIVE_TOKEN_DENOM.to_string());
+    assert!(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).is_err());
+
+    // Migrate the wrapper
+    let _res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    // The "handler" defaults to the "governor"
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.governor, "creator".to_string());
+    assert_eq!(config.handler, "creator".to_string());
+    assert_eq!(config.native_token_denom, NATIVE_TOKEN_DENOM.to_string());
+    assert_eq!(config.proposal_nonce, "0".to_string());
+    assert_eq!(config.unwrap_fee_percentage, "0".to_string());
+    assert_eq!(config.is_unwrap_fee_retained, "false".to_string());
+
+    // The migrated wrapper accepts the proposals & wrapping
+    let info = mock_info("creator", &[]);
+    let add_token_msg = ExecuteMsg::AddCw20TokenAddr {
+        token: CW20_TOKEN.to_string(),
+        nonce: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, add_token_msg).unwrap();
+
+    let info = mock_info("anyone", &coins(100, NATIVE_TOKEN_DENOM));
+    let wrap_msg = ExecuteMsg::Wrap {
+        sender: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, wrap_msg).unwrap();
+    assert_eq!(res.attributes[4], attr("minted", "100"));
+}
@@ -1,8 +1,12 @@
-use cosmwasm_std::{Addr, DepsMut, Uint128};
+use cosmwasm_std::{Addr, Deps, DepsMut, Order, StdError, StdResult, Storage, Uint128};
 use cw20::BalanceResponse;
 use cw20_base::contract::{query_balance, query_token_info};
+use cw20_base::state::TOKEN_INFO;
 
-use crate::state::{CONFIG, TOKENS};
+use crate::state::{
+    read_decimals, read_reserve, CONFIG, MAX_SHARES, NATIVE_TOKENS, RESERVES, TOKENS,
+    TRUSTED_CALLERS,
+};
 use protocol_cosmwasm::token_wrapper::WRAP_FEE_CALC_DENOMINATOR;
 
 // Check if the cw20 token address is valid in "TOKENS".
@@ -10,6 +14,21 @@ pub fn is_valid_address(deps: DepsMut, token_address: Addr) -> bool {
     TOKENS.load(deps.storage, token_address).unwrap_or(false)
 }
 
+// Check if the native token denom is valid in "NATIVE_TOKENS".
+pub fn is_valid_native_denom(deps: Deps, denom: &str) -> bool {
+    NATIVE_TOKENS
+        .load(deps.storage, denom.to_string())
+        .unwrap_or(false)
+}
+
+// Check if the "caller" can wrap/unwrap on behalf of the "owner" without the allowance.
+pub fn is_authorized_caller(deps: Deps, caller: &Addr, owner: &str) -> bool {
+    caller.as_str() == owner
+        || TRUSTED_CALLERS
+            .load(deps.storage, caller.clone())
+            .unwrap_or(false)
+}
+
 // Check if the "wrap_amount" is valid.
 pub fn is_valid_wrap_amount(deps: DepsMut, amount: Uint128) -> bool {
     let total_supply = query_token_info(deps.as_ref()).unwrap().total_supply;
@@ -19,6 +38,26 @@ pub fn is_valid_wrap_amount(deps: DepsMut, amount: Uint128) -> bool {
         .le(&config.wrapping_limit)
 }
 
+// Check if the share of "asset" in the reserves, after wrapping the "minted" amount, is within its max share.
+// The reserves are compared in the wrapped token decimals.
+pub fn is_valid_asset_share(deps: Deps, asset: &str, minted: Uint128) -> StdResult<bool> {
+    let max_share = match MAX_SHARES.may_load(deps.storage, asset.to_string())? {
+        Some(v) => v,
+        None => return Ok(true),
+    };
+    let total_reserve = RESERVES
+        .range(deps.storage, None, None, Order::Ascending)
+        .try_fold(minted, |acc, item| -> StdResult<Uint128> {
+            let (reserve_asset, reserve) = item?;
+            let reserve = to_wrapped_amount(deps.storage, &reserve_asset, reserve)?;
+            Ok(acc.checked_add(reserve)?)
+        })?;
+    let asset_reserve = read_reserve(deps.storage, asset)?;
+    let asset_reserve =
+        to_wrapped_amount(deps.storage, asset, asset_reserve)?.checked_add(minted)?;
+    Ok(asset_reserve <= total_reserve.multiply_ratio(max_share, WRAP_FEE_CALC_DENOMINATOR))
+}
+
 // Check if the "unwrap_amount" is valid.
 pub fn is_valid_unwrap_amount(deps: DepsMut, sender: &str, amount: Uint128) -> bool {
     let sender_token_balance = query_balance(deps.as_ref(), sender.to_string())
@@ -34,6 +73,26 @@ pub fn get_fee_from_amount(amount_to_wrap: Uint128, fee_perc: u16) -> Uint128 {
     amount_to_wrap.multiply_ratio(fee_perc, WRAP_FEE_CALC_DENOMINATOR)
 }
 
+// Calculates the unwrap "fee" & "refund" of underlying "asset" from the unwrapping "amount" of wrapped token.
+pub fn get_unwrap_fee_and_refund(
+    store: &dyn Storage,
+    asset: &str,
+    amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let underlying_amount = to_underlying_amount(store, asset, amount, false)?;
+    split_unwrap_fee(store, underlying_amount)
+}
+
+// Splits the unwrap "fee" & "refund" from the "underlying_amount" of underlying asset.
+pub fn split_unwrap_fee(
+    store: &dyn Storage,
+    underlying_amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let unwrap_fee_percentage = CONFIG.load(store)?.unwrap_fee_percentage;
+    let fee = get_fee_from_amount(underlying_amount, unwrap_fee_percentage);
+    Ok((fee, underlying_amount - fee))
+}
+
 // Calculate the "amount_to_send" from "deposit_target" amount.
 pub fn get_amount_to_wrap(target_amount: Uint128, fee_perc: u16) -> Uint128 {
     target_amount.multiply_ratio(
@@ -41,3 +100,54 @@ pub fn get_amount_to_wrap(target_amount: Uint128, fee_perc: u16) -> Uint128 {
         WRAP_FEE_CALC_DENOMINATOR - fee_perc,
     )
 }
+
+// Converts the "amount" of underlying "asset" into the wrapped token amount, rounding down.
+pub fn to_wrapped_amount(store: &dyn Storage, asset: &str, amount: Uint128) -> StdResult<Uint128> {
+    let asset_decimals = read_decimals(store, asset)?;
+    let decimals = TOKEN_INFO.load(store)?.decimals;
+    scale_amount(amount, asset_decimals, decimals, false)
+}
+
+// Converts the "amount" of wrapped token into the underlying "asset" amount.
+// Rounds down, unless "round_up" is set.
+pub fn to_underlying_amount(
+    store: &dyn Storage,
+    asset: &str,
+    amount: Uint128,
+    round_up: bool,
+) -> StdResult<Uint128> {
+    let asset_decimals = read_decimals(store, asset)?;
+    let decimals = TOKEN_INFO.load(store)?.decimals;
+    scale_amount(amount, decimals, asset_decimals, round_up)
+}
+
+// Scales the "amount" from "from_decimals" to "to_decimals".
+fn scale_amount(
+    amount: Uint128,
+    from_decimals: u8,
+    to_decimals: u8,
+    round_up: bool,
+) -> StdResult<Uint128> {
+    let amount = amount.u128();
+    if from_decimals <= to_decimals {
+        let factor = pow10(to_decimals - from_decimals)?;
+        amount
+            .checked_mul(factor)
+            .map(Uint128::from)
+            .ok_or_else(|| StdError::generic_err("Overflow in scaling the amount"))
+    } else {
+        let factor = pow10(from_decimals - to_decimals)?;
+        let scaled = amount / factor;
+        if round_up && amount % factor != 0 {
+            Ok(Uint128::from(scaled + 1))
+        } else {
+            Ok(Uint128::from(scaled))
+        }
+    }
+}
+
+fn pow10(exp: u8) -> StdResult<u128> {
+    10_u128
+        .checked_pow(exp as u32)
+        .ok_or_else(|| StdError::generic_err("Decimals difference is too large"))
+}
@@ -3,38 +3,42 @@ use std::str::FromStr;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Attribute, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, Event,
-    MessageInfo, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+    attr, coins, from_binary, to_binary, Addr, Attribute, Binary, Coin, CosmosMsg, Deps, DepsMut,
+    Env, Event, MessageInfo, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::events::{hex_encode, CommitmentEvent, NullifierEvent};
+use protocol_cosmwasm::ext_data::hash_ext_data;
 use protocol_cosmwasm::field_ops::{ArkworksIntoFieldBn254, IntoPrimeField};
-use protocol_cosmwasm::keccak::Keccak256;
 use protocol_cosmwasm::poseidon::Poseidon;
 use protocol_cosmwasm::structs::{
-    Edge, EdgeInfoResponse, MerkleRootInfoResponse, MerkleTreeInfoResponse,
-    NeighborRootInfoResponse, COSMOS_CHAIN_TYPE, HISTORY_LENGTH,
+    Edge, EdgeInfoResponse, EdgeState, EdgesResponse, IsKnownNullifierResponse,
+    IsKnownRootResponse, LeafIndexResponse, LeavesResponse, MerklePathResponse,
+    MerkleRootInfoResponse, MerkleTreeInfoResponse, NeighborRootInfoResponse, RootHistoryResponse,
+    SpentNullifiersResponse, COSMOS_CHAIN_TYPE, DEFAULT_LEAVES_LIMIT, HISTORY_LENGTH,
+    MAX_LEAVES_LIMIT,
 };
 use protocol_cosmwasm::token_wrapper::{
     ConfigResponse as TokenWrapperConfigResp, Cw20HookMsg as TokenWrapperHookMsg,
-    ExecuteMsg as TokenWrapperExecuteMsg, GetAmountToWrapResponse,
+    ExecuteMsg as TokenWrapperExecuteMsg, FeeFromUnwrapAmountResponse, GetAmountToWrapResponse,
     QueryMsg as TokenWrapperQueryMsg,
 };
 use protocol_cosmwasm::utils::{compute_chain_id, compute_chain_id_type, element_encoder};
 use protocol_cosmwasm::vanchor::{
     ConfigResponse, Cw20HookMsg, ExecuteMsg, ExtData, InstantiateMsg, MigrateMsg, ProofData,
-    QueryMsg, UpdateConfigMsg,
+    PublicKeyResponse, QueryMsg, UpdateConfigMsg,
 };
 use protocol_cosmwasm::vanchor_verifier::VAnchorVerifier;
 use protocol_cosmwasm::zeroes::zeroes;
 
 use crate::state::{
-    read_curr_neighbor_root_index, read_edge, read_neighbor_roots, read_root,
-    save_curr_neighbor_root_index, save_edge, save_neighbor_roots, save_root, save_subtree,
-    LinkableMerkleTree, MerkleTree, VAnchor, HASHER, NULLIFIERS, VANCHOR, VERIFIER_16_2,
-    VERIFIER_2_2,
+    read_curr_neighbor_root_index, read_edge, read_leaf, read_leaf_index, read_neighbor_roots,
+    read_root, save_curr_neighbor_root_index, save_edge, save_neighbor_roots, save_root,
+    save_subtree, LinkableMerkleTree, MerkleTree, VAnchor, HASHER, NULLIFIERS, PUBLIC_KEYS,
+    VANCHOR, VERIFIER_16_2, VERIFIER_2_2,
 };
 
 // version info for migration info
@@ -81,6 +85,7 @@ pub fn instantiate(
         levels: msg.levels,
         current_root_index: 0,
         next_index: 0,
+        tree_id: 0,
     };
 
     // Initialize the linkable merkle tree
@@ -103,6 +108,7 @@ pub fn instantiate(
         tokenwrapper_addr,
         handler: deps.api.addr_validate(&msg.handler)?,
         proposal_nonce: 0_u32,
+        ext_data_encoding: msg.ext_data_encoding.unwrap_or_default(),
     };
     VANCHOR.save(deps.storage, &anchor)?;
 
@@ -213,9 +219,41 @@ pub fn execute(
         ExecuteMsg::ConfigureMinimalWithdrawalLimit {
             minimal_withdrawal_amount,
         } => config_min_withdraw_limit(deps, info, minimal_withdrawal_amount),
+
+        // Registers the shielded account public key of the tx sender
+        ExecuteMsg::Register { owner, public_key } => {
+            register(deps, info.sender.to_string(), owner, public_key)
+        }
     }
 }
 
+// Registers the shielded account "public_key" of the "owner"
+fn register(
+    deps: DepsMut,
+    sender: String,
+    owner: String,
+    public_key: Vec<u8>,
+) -> Result<Response, ContractError> {
+    // Only the "owner" can register its own public key
+    let owner = deps.api.addr_validate(&owner)?;
+    if owner.as_str() != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    // Shielded account key is either 32 bytes or 64 bytes(with the encryption key)
+    if public_key.len() != 32 && public_key.len() != 64 {
+        return Err(ContractError::InvalidPublicKey);
+    }
+
+    PUBLIC_KEYS.save(deps.storage, owner.to_string(), &public_key)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![attr("action", "register")])
+        .add_event(Event::new("vanchor-public-key").add_attributes(vec![
+            attr("owner", owner),
+            attr("public_key", hex_encode(&public_key)),
+        ])))
+}
+
 fn config_max_deposit_limit(
     deps: DepsMut,
     info: MessageInfo,
@@ -275,13 +313,17 @@ fn update_vanchor_config(
         vanchor.max_fee = max_fee;
     }
 
+    if let Some(ext_data_encoding) = msg.ext_data_encoding {
+        vanchor.ext_data_encoding = ext_data_encoding;
+    }
+
     VANCHOR.save(deps.storage, &vanchor)?;
 
     Ok(Response::new().add_attributes(vec![attr("action", "update_vanchor_config")]))
 }
 
 fn receive_cw20(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
@@ -321,6 +363,25 @@ fn receive_cw20(
             recv_token_addr,
             recv_token_amt,
         ),
+        Ok(Cw20HookMsg::RegisterAndTransactDeposit {
+            owner,
+            public_key,
+            proof_data,
+            ext_data,
+        }) => {
+            let register_res = register(deps.branch(), sender, owner, public_key)?;
+            let transact_res = transact_deposit(
+                deps,
+                env,
+                proof_data,
+                ext_data,
+                recv_token_addr,
+                recv_token_amt,
+            )?;
+            Ok(transact_res
+                .add_attributes(register_res.attributes)
+                .add_events(register_res.events))
+        }
         Err(_) => Err(ContractError::InvalidCw20HookMsg),
     }
 }
@@ -343,20 +404,20 @@ fn transact_deposit(
     validate_proof(deps.branch(), env, proof_data.clone(), ext_data.clone())?;
 
     let ext_data_fee: u128 = ext_data.fee.u128();
-    let ext_amt: i128 = ext_data.ext_amount.parse().expect("Invalid ext_amount");
+    let ext_amt = ext_data.ext_amount;
     let abs_ext_amt = ext_amt.unsigned_abs();
 
     // Deposit
     let mut msgs: Vec<CosmosMsg> = vec![];
 
-    let is_withdraw = ext_amt.is_negative();
+    let is_withdraw = ext_amt.is_withdraw();
     if is_withdraw {
         return Err(ContractError::InvalidExecutionEntry);
     } else {
-        if Uint128::from(abs_ext_amt) > vanchor.max_deposit_amt {
+        if abs_ext_amt > vanchor.max_deposit_amt {
             return Err(ContractError::InvalidDepositAmount);
         };
-        if abs_ext_amt != recv_token_amt.u128() {
+        if abs_ext_amt != recv_token_amt {
             return Err(ContractError::InsufficientFunds {});
         };
         // No need to call "transfer from transactor to this contract"
@@ -371,40 +432,30 @@ fn transact_deposit(
             contract_addr: vanchor.tokenwrapper_addr.to_string(),
             funds: [].to_vec(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: ext_data.relayer,
+                recipient: ext_data.relayer.clone(),
                 amount: Uint128::try_from(ext_data_fee).unwrap(),
             })?,
         }));
     }
 
-    execute_insertions(deps, &proof_data)?;
-
-    let new_commitments: Vec<Attribute> = vec![
-        attr(
-            format!("{:?}", proof_data.output_commitments[0]),
-            format!("{:?}", ext_data.encrypted_output1),
-        ),
-        attr(
-            format!("{:?}", proof_data.output_commitments[1]),
-            format!("{:?}", ext_data.encrypted_output2),
-        ),
-    ];
-
-    let nullifiers = proof_data
+    let commitment_events = execute_insertions(deps, &proof_data, &ext_data)?;
+    let nullifier_events = proof_data
         .input_nullifiers
         .iter()
-        .map(|null| attr("nullifier_hash", format!("{:?}", null)))
-        .collect::<Vec<Attribute>>();
-
-    Ok(Response::new().add_messages(msgs).add_event(
-        Event::new("vanchor-deposit")
-            .add_attributes(vec![
-                attr("action", "transact_deposit"),
-                attr("ext_amt", ext_amt.to_string()),
-            ])
-            .add_attributes(new_commitments)
-            .add_attributes(nullifiers),
-    ))
+        .map(|nullifier| NullifierEvent::new("vanchor", *nullifier).build());
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_event(
+            Event::new("vanchor-deposit")
+                .add_attributes(vec![
+                    attr("action", "transact_deposit"),
+                    attr("ext_amt", ext_amt.to_string()),
+                ])
+                .add_attributes(transact_attributes(&proof_data, &ext_data)),
+        )
+        .add_events(commitment_events)
+        .add_events(nullifier_events))
 }
 
 // Executes a deposit(native token) or combination join/split transaction
@@ -417,7 +468,7 @@ fn transact_deposit_wrap_native(
     ext_data: ExtData,
 ) -> Result<Response, ContractError> {
     let ext_data_fee: u128 = ext_data.fee.u128();
-    let ext_amt: i128 = ext_data.ext_amount.parse().expect("Invalid ext_amount");
+    let ext_amt = ext_data.ext_amount;
     let abs_ext_amt = ext_amt.unsigned_abs();
 
     let vanchor: VAnchor = VANCHOR.load(deps.storage)?;
@@ -439,6 +490,7 @@ fn transact_deposit_wrap_native(
         vanchor.tokenwrapper_addr.to_string(),
         &TokenWrapperQueryMsg::GetAmountToWrap {
             target_amount: abs_ext_amt.to_string(),
+            token: None,
         },
     )?;
     let amt_to_wrap = Uint128::from_str(&amt_to_wrap_query.amount_to_wrap)?;
@@ -453,11 +505,11 @@ fn transact_deposit_wrap_native(
     // Deposit
     let mut msgs: Vec<CosmosMsg> = vec![];
 
-    let is_withdraw = ext_amt.is_negative();
+    let is_withdraw = ext_amt.is_withdraw();
     if is_withdraw {
         return Err(ContractError::InvalidExecutionEntry);
     } else {
-        if Uint128::from(abs_ext_amt) > vanchor.max_deposit_amt {
+        if abs_ext_amt > vanchor.max_deposit_amt {
             return Err(ContractError::InvalidDepositAmount);
         };
         msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -478,40 +530,30 @@ fn transact_deposit_wrap_native(
             contract_addr: vanchor.tokenwrapper_addr.to_string(),
             funds: [].to_vec(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: ext_data.relayer,
+                recipient: ext_data.relayer.clone(),
                 amount: Uint128::try_from(ext_data_fee).unwrap(),
             })?,
         }));
     }
 
-    execute_insertions(deps, &proof_data)?;
-
-    let new_commitments: Vec<Attribute> = vec![
-        attr(
-            format!("{:?}", proof_data.output_commitments[0]),
-            format!("{:?}", ext_data.encrypted_output1),
-        ),
-        attr(
-            format!("{:?}", proof_data.output_commitments[1]),
-            format!("{:?}", ext_data.encrypted_output2),
-        ),
-    ];
-
-    let nullifiers = proof_data
+    let commitment_events = execute_insertions(deps, &proof_data, &ext_data)?;
+    let nullifier_events = proof_data
         .input_nullifiers
         .iter()
-        .map(|null| attr("nullifier_hash", format!("{:?}", null)))
-        .collect::<Vec<Attribute>>();
-
-    Ok(Response::new().add_messages(msgs).add_event(
-        Event::new("vanchor-deposit")
-            .add_attributes(vec![
-                attr("action", "transact_deposit_wrap_native"),
-                attr("ext_amt", ext_amt.to_string()),
-            ])
-            .add_attributes(new_commitments)
-            .add_attributes(nullifiers),
-    ))
+        .map(|nullifier| NullifierEvent::new("vanchor", *nullifier).build());
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_event(
+            Event::new("vanchor-deposit")
+                .add_attributes(vec![
+                    attr("action", "transact_deposit_wrap_native"),
+                    attr("ext_amt", ext_amt.to_string()),
+                ])
+                .add_attributes(transact_attributes(&proof_data, &ext_data)),
+        )
+        .add_events(commitment_events)
+        .add_events(nullifier_events))
 }
 
 // Executes a deposit(cw20) or combination join/split transaction
@@ -525,7 +567,7 @@ fn transact_deposit_wrap_cw20(
     recv_token_amt: Uint128,
 ) -> Result<Response, ContractError> {
     let ext_data_fee: u128 = ext_data.fee.u128();
-    let ext_amt: i128 = ext_data.ext_amount.parse().expect("Invalid ext_amount");
+    let ext_amt = ext_data.ext_amount;
     let abs_ext_amt = ext_amt.unsigned_abs();
 
     // Only non-"TokenWrapper" Cw20 token contract can execute this message.
@@ -539,6 +581,7 @@ fn transact_deposit_wrap_cw20(
         vanchor.tokenwrapper_addr.to_string(),
         &TokenWrapperQueryMsg::GetAmountToWrap {
             target_amount: abs_ext_amt.to_string(),
+            token: Some(recv_token_addr.clone()),
         },
     )?;
     let amt_to_wrap = Uint128::from_str(&amt_to_wrap_query.amount_to_wrap)?;
@@ -553,10 +596,10 @@ fn transact_deposit_wrap_cw20(
     // Deposit
     let mut msgs: Vec<CosmosMsg> = vec![];
 
-    if ext_amt.is_negative() {
+    if ext_amt.is_withdraw() {
         return Err(ContractError::InvalidExecutionEntry);
     } else {
-        if Uint128::from(abs_ext_amt) > vanchor.max_deposit_amt {
+        if abs_ext_amt > vanchor.max_deposit_amt {
             return Err(ContractError::InvalidDepositAmount);
         };
         msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -581,40 +624,30 @@ fn transact_deposit_wrap_cw20(
             contract_addr: vanchor.tokenwrapper_addr.to_string(),
             funds: [].to_vec(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: ext_data.relayer,
+                recipient: ext_data.relayer.clone(),
                 amount: Uint128::try_from(ext_data_fee).unwrap(),
             })?,
         }));
     }
 
-    execute_insertions(deps.branch(), &proof_data)?;
-
-    let new_commitments: Vec<Attribute> = vec![
-        attr(
-            format!("{:?}", proof_data.output_commitments[0]),
-            format!("{:?}", ext_data.encrypted_output1),
-        ),
-        attr(
-            format!("{:?}", proof_data.output_commitments[1]),
-            format!("{:?}", ext_data.encrypted_output2),
-        ),
-    ];
-
-    let nullifiers = proof_data
+    let commitment_events = execute_insertions(deps.branch(), &proof_data, &ext_data)?;
+    let nullifier_events = proof_data
         .input_nullifiers
         .iter()
-        .map(|null| attr("nullifier_hash", format!("{:?}", null)))
-        .collect::<Vec<Attribute>>();
-
-    Ok(Response::new().add_messages(msgs).add_event(
-        Event::new("vanchor-deposit")
-            .add_attributes(vec![
-                attr("action", "transact_deposit_wrap_cw20"),
-                attr("ext_amt", ext_amt.to_string()),
-            ])
-            .add_attributes(new_commitments)
-            .add_attributes(nullifiers),
-    ))
+        .map(|nullifier| NullifierEvent::new("vanchor", *nullifier).build());
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_event(
+            Event::new("vanchor-deposit")
+                .add_attributes(vec![
+                    attr("action", "transact_deposit_wrap_cw20"),
+                    attr("ext_amt", ext_amt.to_string()),
+                ])
+                .add_attributes(transact_attributes(&proof_data, &ext_data)),
+        )
+        .add_events(commitment_events)
+        .add_events(nullifier_events))
 }
 
 // Executes a deposit/withdrawal or combination join/split transaction
@@ -628,25 +661,34 @@ fn transact_withdraw(
 
     let vanchor = VANCHOR.load(deps.storage)?;
     let ext_data_fee: u128 = ext_data.fee.u128();
-    let ext_amt: i128 = ext_data.ext_amount.parse().expect("Invalid ext_amount");
+    let ext_amt = ext_data.ext_amount;
     let abs_ext_amt = ext_amt.unsigned_abs();
 
     // Withdraw
     let mut msgs: Vec<CosmosMsg> = vec![];
 
-    if ext_amt.is_positive() {
+    if ext_amt.is_deposit() {
         return Err(ContractError::InvalidExecutionEntry);
     } else {
-        if Uint128::from(abs_ext_amt) < vanchor.min_withdraw_amt {
+        if abs_ext_amt < vanchor.min_withdraw_amt {
             return Err(ContractError::InvalidWithdrawAmount);
         }
+        // Deliver the tokens to the "recipient" contract along with the hook payload, if given
+        let withdraw_msg = match ext_data.hook.clone() {
+            Some(hook) => Cw20ExecuteMsg::Send {
+                contract: ext_data.recipient.clone(),
+                amount: abs_ext_amt,
+                msg: hook,
+            },
+            None => Cw20ExecuteMsg::Transfer {
+                recipient: ext_data.recipient.clone(),
+                amount: abs_ext_amt,
+            },
+        };
         msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: vanchor.tokenwrapper_addr.to_string(),
             funds: [].to_vec(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: ext_data.recipient.clone(),
-                amount: Uint128::try_from(abs_ext_amt).unwrap(),
-            })?,
+            msg: to_binary(&withdraw_msg)?,
         }));
     }
 
@@ -658,40 +700,30 @@ fn transact_withdraw(
             contract_addr: vanchor.tokenwrapper_addr.to_string(),
             funds: [].to_vec(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: ext_data.relayer,
+                recipient: ext_data.relayer.clone(),
                 amount: Uint128::try_from(ext_data_fee).unwrap(),
             })?,
         }));
     }
 
-    execute_insertions(deps, &proof_data)?;
-
-    let new_commitments: Vec<Attribute> = vec![
-        attr(
-            format!("{:?}", proof_data.output_commitments[0]),
-            format!("{:?}", ext_data.encrypted_output1),
-        ),
-        attr(
-            format!("{:?}", proof_data.output_commitments[1]),
-            format!("{:?}", ext_data.encrypted_output2),
-        ),
-    ];
-
-    let nullifiers = proof_data
+    let commitment_events = execute_insertions(deps, &proof_data, &ext_data)?;
+    let nullifier_events = proof_data
         .input_nullifiers
         .iter()
-        .map(|null| attr("nullifier_hash", format!("{:?}", null)))
-        .collect::<Vec<Attribute>>();
-
-    Ok(Response::new().add_messages(msgs).add_event(
-        Event::new("vanchor-withdraw")
-            .add_attributes(vec![
-                attr("action", "transact_withdraw"),
-                attr("ext_amt", ext_amt.to_string()),
-            ])
-            .add_attributes(new_commitments)
-            .add_attributes(nullifiers),
-    ))
+        .map(|nullifier| NullifierEvent::new("vanchor", *nullifier).build());
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_event(
+            Event::new("vanchor-withdraw")
+                .add_attributes(vec![
+                    attr("action", "transact_withdraw"),
+                    attr("ext_amt", ext_amt.to_string()),
+                ])
+                .add_attributes(transact_attributes(&proof_data, &ext_data)),
+        )
+        .add_events(commitment_events)
+        .add_events(nullifier_events))
 }
 
 // Executes a withdrawal(native + cw20) or combination join/split transaction
@@ -703,32 +735,54 @@ fn transact_withdraw_unwrap(
     ext_data: ExtData,
     token_addr: Option<String>,
 ) -> Result<Response, ContractError> {
+    let this = env.contract.address.to_string();
     validate_proof(deps.branch(), env, proof_data.clone(), ext_data.clone())?;
 
     let vanchor = VANCHOR.load(deps.storage)?;
     let ext_data_fee: u128 = ext_data.fee.u128();
-    let ext_amt: i128 = ext_data.ext_amount.parse().expect("Invalid ext_amount");
+    let ext_amt = ext_data.ext_amount;
     let abs_ext_amt = ext_amt.unsigned_abs();
 
     // Withdraw
     let mut msgs: Vec<CosmosMsg> = vec![];
 
-    if ext_amt.is_positive() {
+    if ext_amt.is_deposit() {
         return Err(ContractError::InvalidExecutionEntry);
     } else {
-        if Uint128::from(abs_ext_amt) < vanchor.min_withdraw_amt {
+        if abs_ext_amt < vanchor.min_withdraw_amt {
             return Err(ContractError::InvalidWithdrawAmount);
         }
+        let token = token_addr
+            .map(|a| deps.api.addr_validate(a.as_str()))
+            .transpose()?;
+
+        // With the hook, the tokens are unwrapped to this contract first,
+        // and then delivered to the "recipient" contract along with the hook payload.
+        let unwrap_recipient = match ext_data.hook {
+            Some(_) => this,
+            None => ext_data.recipient.clone(),
+        };
         msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: vanchor.tokenwrapper_addr.to_string(),
             funds: [].to_vec(),
             msg: to_binary(&TokenWrapperExecuteMsg::Unwrap {
                 sender: None,
-                recipient: Some(ext_data.recipient),
-                token: token_addr.map(|a| deps.api.addr_validate(a.as_str()).unwrap()),
-                amount: Uint128::try_from(abs_ext_amt).unwrap(),
+                recipient: Some(unwrap_recipient),
+                token: token.clone(),
+                denom: None,
+                amount: abs_ext_amt,
             })?,
         }));
+        if let Some(hook) = ext_data.hook.clone() {
+            msgs.push(unwrapped_hook_msg(
+                deps.as_ref(),
+                &vanchor.tokenwrapper_addr,
+                token,
+                ext_data.recipient.clone(),
+                abs_ext_amt,
+                hook,
+            )?);
+        }
     }
 
     // If fee exists, handle it
@@ -739,40 +793,73 @@ fn transact_withdraw_unwrap(
             contract_addr: vanchor.tokenwrapper_addr.to_string(),
             funds: [].to_vec(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: ext_data.relayer,
+                recipient: ext_data.relayer.clone(),
                 amount: Uint128::try_from(ext_data_fee).unwrap(),
             })?,
         }));
     }
 
-    execute_insertions(deps, &proof_data)?;
-
-    let new_commitments: Vec<Attribute> = vec![
-        attr(
-            format!("{:?}", proof_data.output_commitments[0]),
-            format!("{:?}", ext_data.encrypted_output1),
-        ),
-        attr(
-            format!("{:?}", proof_data.output_commitments[1]),
-            format!("{:?}", ext_data.encrypted_output2),
-        ),
-    ];
-
-    let nullifiers = proof_data
+    let commitment_events = execute_insertions(deps, &proof_data, &ext_data)?;
+    let nullifier_events = proof_data
         .input_nullifiers
         .iter()
-        .map(|null| attr("nullifier_hash", format!("{:?}", null)))
-        .collect::<Vec<Attribute>>();
-
-    Ok(Response::new().add_messages(msgs).add_event(
-        Event::new("vanchor-withdraw")
-            .add_attributes(vec![
-                attr("action", "transact_withdraw_unwrap"),
-                attr("ext_amt", ext_amt.to_string()),
-            ])
-            .add_attributes(new_commitments)
-            .add_attributes(nullifiers),
-    ))
+        .map(|nullifier| NullifierEvent::new("vanchor", *nullifier).build());
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_event(
+            Event::new("vanchor-withdraw")
+                .add_attributes(vec![
+                    attr("action", "transact_withdraw_unwrap"),
+                    attr("ext_amt", ext_amt.to_string()),
+                ])
+                .add_attributes(transact_attributes(&proof_data, &ext_data)),
+        )
+        .add_events(commitment_events)
+        .add_events(nullifier_events))
+}
+
+// Delivers the unwrapped tokens to the "recipient" contract along with the "hook" payload.
+// The delivered amount is the "refund" of unwrapping the "amount", excluding the unwrap fee.
+fn unwrapped_hook_msg(
+    deps: Deps,
+    tokenwrapper_addr: &Addr,
+    token: Option<Addr>,
+    recipient: String,
+    amount: Uint128,
+    hook: Binary,
+) -> StdResult<CosmosMsg> {
+    let unwrap_fee_query: FeeFromUnwrapAmountResponse = deps.querier.query_wasm_smart(
+        tokenwrapper_addr.to_string(),
+        &TokenWrapperQueryMsg::FeeFromUnwrapAmount {
+            amount_to_unwrap: amount.to_string(),
+            token: token.as_ref().map(|token| token.to_string()),
+        },
+    )?;
+    let amount = Uint128::from_str(&unwrap_fee_query.refund_amt)?;
+
+    match token {
+        Some(token) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: recipient,
+                amount,
+                msg: hook,
+            })?,
+        })),
+        None => {
+            let wrapper_config: TokenWrapperConfigResp = deps.querier.query_wasm_smart(
+                tokenwrapper_addr.to_string(),
+                &TokenWrapperQueryMsg::Config {},
+            )?;
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: recipient,
+                funds: coins(amount.u128(), wrapper_config.native_token_denom),
+                msg: hook,
+            }))
+        }
+    }
 }
 
 // Check whether if the zkSNARK proof is valid
@@ -785,7 +872,7 @@ fn validate_proof(
     let vanchor = VANCHOR.load(deps.storage)?;
 
     let ext_data_fee: u128 = ext_data.fee.u128();
-    let ext_amt: i128 = ext_data.ext_amount.parse().expect("Invalid ext_amount");
+    let ext_amt = ext_data.ext_amount;
 
     // Validation 1. Double check the number of roots.
     if vanchor.linkable_tree.max_edges != proof_data.roots.len() as u32 {
@@ -815,22 +902,9 @@ fn validate_proof(
         }
     }
 
-    // Compute hash of abi encoded ext_data, reduced into field from config
+    // Compute hash of encoded ext_data(in the layout of pool), reduced into field
     // Ensure that the passed external data hash matches the computed one
-    let mut ext_data_args = Vec::new();
-    let recipient_bytes = element_encoder(ext_data.recipient.as_bytes());
-    let relayer_bytes = element_encoder(ext_data.relayer.as_bytes());
-    let fee_bytes = element_encoder(&ext_data_fee.to_le_bytes());
-    let ext_amt_bytes = element_encoder(&ext_amt.to_le_bytes());
-    ext_data_args.extend_from_slice(&recipient_bytes);
-    ext_data_args.extend_from_slice(&relayer_bytes);
-    ext_data_args.extend_from_slice(&ext_amt_bytes);
-    ext_data_args.extend_from_slice(&fee_bytes);
-    ext_data_args.extend_from_slice(&ext_data.encrypted_output1);
-    ext_data_args.extend_from_slice(&ext_data.encrypted_output2);
-
-    let computed_ext_data_hash =
-        Keccak256::hash(&ext_data_args).map_err(|_| ContractError::HashError)?;
+    let computed_ext_data_hash = hash_ext_data(deps.api, &ext_data, vanchor.ext_data_encoding)?;
     if computed_ext_data_hash != proof_data.ext_data_hash {
         return Err(ContractError::InvalidExtData);
     }
@@ -841,14 +915,14 @@ fn validate_proof(
         return Err(ContractError::InvalidFeeAmount);
     }
 
-    if Uint128::from(abs_ext_amt) > vanchor.max_ext_amt {
+    if abs_ext_amt > vanchor.max_ext_amt {
         return Err(ContractError::InvalidExtAmount);
     }
 
     // Public amounnt can also be negative, in which
     // case it would wrap around the field, so we should check if FIELD_SIZE -
     // public_amount == proof_data.public_amount, in case of a negative ext_amount
-    let calc_public_amt = ext_amt - ext_data_fee as i128;
+    let calc_public_amt = ext_amt.i128() - ext_data_fee as i128;
     let calc_public_amt_bytes =
         element_encoder(&ArkworksIntoFieldBn254::into_field(calc_public_amt));
     if calc_public_amt_bytes != proof_data.public_amount {
@@ -898,15 +972,56 @@ fn validate_proof(
     Ok(())
 }
 
-// Inserts the output commitments into the underlying merkle tree
-fn execute_insertions(deps: DepsMut, proof_data: &ProofData) -> Result<(), ContractError> {
+// Attributes of the new commitments(with encrypted outputs) & spent nullifiers,
+// kept along with the structured events for the existing indexers & relayers.
+fn transact_attributes(proof_data: &ProofData, ext_data: &ExtData) -> Vec<Attribute> {
+    let mut attributes = vec![
+        attr(
+            format!("{:?}", proof_data.output_commitments[0]),
+            format!("{:?}", ext_data.encrypted_output1),
+        ),
+        attr(
+            format!("{:?}", proof_data.output_commitments[1]),
+            format!("{:?}", ext_data.encrypted_output2),
+        ),
+    ];
+    attributes.extend(
+        proof_data
+            .input_nullifiers
+            .iter()
+            .map(|null| attr("nullifier_hash", format!("{:?}", null))),
+    );
+    attributes
+}
+
+// Inserts the output commitments into the underlying merkle tree.
+// Returns the events of inserted commitments.
+fn execute_insertions(
+    deps: DepsMut,
+    proof_data: &ProofData,
+    ext_data: &ExtData,
+) -> Result<Vec<Event>, ContractError> {
     let vanchor = VANCHOR.load(deps.storage)?;
+    let encrypted_outputs = [&ext_data.encrypted_output1, &ext_data.encrypted_output2];
     // Insert output commitments into the tree
     let mut merkle_tree = vanchor.merkle_tree;
-    for comm in &proof_data.output_commitments {
-        let poseidon: Poseidon = HASHER.load(deps.storage)?;
-        merkle_tree.insert(poseidon, *comm, deps.storage)?;
-    }
+    // The output commitments are inserted at once as an aligned subtree.
+    let poseidon: Poseidon = HASHER.load(deps.storage)?;
+    let first_index =
+        merkle_tree.insert_subtree(poseidon, &proof_data.output_commitments, deps.storage)?;
+    let new_root = read_root(deps.storage, merkle_tree.current_root_index)?;
+    let events: Vec<Event> = proof_data
+        .output_commitments
+        .iter()
+        .zip(encrypted_outputs.iter())
+        .enumerate()
+        .map(|(i, (comm, encrypted_output))| {
+            CommitmentEvent::new("vanchor", *comm, first_index + i as u32, new_root)
+                .tree_id(merkle_tree.tree_id)
+                .encrypted_output(encrypted_output)
+                .build()
+        })
+        .collect();
 
     VANCHOR.save(
         deps.storage,
@@ -921,9 +1036,10 @@ fn execute_insertions(deps: DepsMut, proof_data: &ProofData) -> Result<(), Contr
             max_ext_amt: vanchor.max_ext_amt,
             handler: vanchor.handler,
             proposal_nonce: vanchor.proposal_nonce,
+            ext_data_encoding: vanchor.ext_data_encoding,
         },
     )?;
-    Ok(())
+    Ok(events)
 }
 
 // Wrap the native token for the `info.sender` into the TokenWrapper token
@@ -984,6 +1100,7 @@ fn unwrap_native(
             sender: Some(sender),
             recipient: Some(recipient),
             token: None,
+            denom: None,
             amount,
         })?,
     })];
@@ -1049,6 +1166,7 @@ fn unwrap_into_token(
             sender: Some(sender),
             recipient: Some(recipient),
             token: Some(deps.api.addr_validate(token_addr.as_str())?),
+            denom: None,
             amount,
         })?,
     })];
@@ -1068,11 +1186,12 @@ fn add_edge(
     latest_leaf_id: u32,
     target: [u8; 32],
 ) -> Result<Response, ContractError> {
-    let vanchor = VANCHOR.load(deps.storage)?;
-    let linkable_tree = vanchor.linkable_tree;
+    let mut vanchor = VANCHOR.load(deps.storage)?;
 
-    let curr_length = linkable_tree.get_latest_neighbor_edges(deps.storage).len();
-    if curr_length > linkable_tree.max_edges as usize {
+    // `max_edges` counts the vanchor itself, since the `roots` of transaction
+    // hold the local root followed by one root per edge.
+    let curr_length = vanchor.linkable_tree.chain_id_list.len();
+    if curr_length + 1 >= vanchor.linkable_tree.max_edges as usize {
         return Err(ContractError::TooManyEdges);
     }
 
@@ -1084,18 +1203,14 @@ fn add_edge(
         latest_leaf_index: latest_leaf_id,
     };
 
-    // update historical neighbor list for this edge's root
-    let curr_neighbor_root_idx = read_curr_neighbor_root_index(deps.storage, src_chain_id)?;
-    save_curr_neighbor_root_index(
-        deps.storage,
-        src_chain_id,
-        (curr_neighbor_root_idx + 1) % HISTORY_LENGTH,
-    )?;
-
-    save_neighbor_roots(deps.storage, (src_chain_id, curr_neighbor_root_idx), root)?;
+    // start historical neighbor list for this edge's root
+    save_curr_neighbor_root_index(deps.storage, src_chain_id, 0)?;
+    save_neighbor_roots(deps.storage, (src_chain_id, 0), root)?;
 
     // Append new edge to the end of the edge list for the given tree
     save_edge(deps.storage, src_chain_id, edge)?;
+    vanchor.linkable_tree.chain_id_list.push(src_chain_id);
+    VANCHOR.save(deps.storage, &vanchor)?;
 
     Ok(
         Response::new().add_event(Event::new("vanchor-edge_add").add_attributes(vec![
@@ -1192,11 +1307,37 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&get_config(deps)?),
         QueryMsg::EdgeInfo { id } => to_binary(&get_edge_info(deps, id)?),
+        QueryMsg::Edges {} => to_binary(&get_edges(deps)?),
         QueryMsg::NeighborRootInfo { chain_id, id } => {
             to_binary(&get_neighbor_root_info(deps, chain_id, id)?)
         }
         QueryMsg::MerkleTreeInfo {} => to_binary(&get_merkle_tree_info(deps)?),
         QueryMsg::MerkleRootInfo { id } => to_binary(&get_merkle_root(deps, id)?),
+        QueryMsg::Leaves {
+            tree_id,
+            start_index,
+            limit,
+        } => to_binary(&get_leaves(deps, tree_id, start_index, limit)?),
+        QueryMsg::LeafIndex { commitment } => to_binary(&get_leaf_index(deps, commitment)?),
+        QueryMsg::MerklePath {
+            tree_id,
+            leaf_index,
+        } => to_binary(&get_merkle_path(deps, tree_id, leaf_index)?),
+        QueryMsg::IsKnownNullifier { nullifier } => {
+            to_binary(&get_is_known_nullifier(deps, nullifier)?)
+        }
+        QueryMsg::SpentNullifiers { nullifiers } => {
+            to_binary(&get_spent_nullifiers(deps, nullifiers)?)
+        }
+        QueryMsg::IsKnownRoot { root } => to_binary(&get_is_known_root(deps, root)?),
+        QueryMsg::RootHistory {} => to_binary(&get_root_history(deps)?),
+        QueryMsg::IsKnownNeighborRoot { chain_id, root } => {
+            to_binary(&get_is_known_neighbor_root(deps, chain_id, root)?)
+        }
+        QueryMsg::NeighborRootHistory { chain_id } => {
+            to_binary(&get_neighbor_root_history(deps, chain_id)?)
+        }
+        QueryMsg::PublicKey { owner } => to_binary(&get_public_key(deps, owner)?),
     }
 }
 
@@ -1210,6 +1351,7 @@ pub fn get_config(deps: Deps) -> StdResult<ConfigResponse> {
         min_withdraw_amt: vanchor.min_withdraw_amt.to_string(),
         max_ext_amt: vanchor.max_ext_amt.to_string(),
         max_fee: vanchor.max_fee.to_string(),
+        ext_data_encoding: vanchor.ext_data_encoding,
     })
 }
 
@@ -1223,6 +1365,27 @@ pub fn get_edge_info(deps: Deps, id: u64) -> StdResult<EdgeInfoResponse> {
     })
 }
 
+pub fn get_edges(deps: Deps) -> StdResult<EdgesResponse> {
+    let vanchor = VANCHOR.load(deps.storage)?;
+    let edges = vanchor
+        .linkable_tree
+        .get_latest_neighbor_edges(deps.storage)
+        .into_iter()
+        .map(|edge| EdgeState {
+            src_chain_id: edge.src_chain_id,
+            root: edge.root,
+            latest_leaf_index: edge.latest_leaf_index,
+            target: edge.target,
+            curr_neighbor_root_index: read_curr_neighbor_root_index(
+                deps.storage,
+                edge.src_chain_id,
+            )
+            .unwrap_or_default(),
+        })
+        .collect();
+    Ok(EdgesResponse { edges })
+}
+
 pub fn get_neighbor_root_info(
     deps: Deps,
     chain_id: u64,
@@ -1238,6 +1401,7 @@ pub fn get_merkle_tree_info(deps: Deps) -> StdResult<MerkleTreeInfoResponse> {
         levels: vanchor.merkle_tree.levels,
         curr_root_index: vanchor.merkle_tree.current_root_index,
         next_index: vanchor.merkle_tree.next_index,
+        tree_id: vanchor.merkle_tree.tree_id,
     })
 }
 
@@ -1246,7 +1410,126 @@ pub fn get_merkle_root(deps: Deps, id: u32) -> StdResult<MerkleRootInfoResponse>
     Ok(MerkleRootInfoResponse { root })
 }
 
+pub fn get_leaves(
+    deps: Deps,
+    tree_id: Option<u32>,
+    start_index: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<LeavesResponse> {
+    let vanchor = VANCHOR.load(deps.storage)?;
+    let tree_id = tree_id.unwrap_or(vanchor.merkle_tree.tree_id);
+    let start_index = start_index.unwrap_or_default();
+    let limit = limit.unwrap_or(DEFAULT_LEAVES_LIMIT).min(MAX_LEAVES_LIMIT);
+    let end_index = start_index
+        .saturating_add(limit)
+        .min(vanchor.merkle_tree.tree_size(tree_id));
+    let leaves = (start_index..end_index)
+        .map(|i| read_leaf(deps.storage, tree_id, i))
+        .collect::<StdResult<Vec<[u8; 32]>>>()?;
+    Ok(LeavesResponse {
+        tree_id,
+        start_index,
+        leaves,
+    })
+}
+
+pub fn get_leaf_index(deps: Deps, commitment: [u8; 32]) -> StdResult<LeafIndexResponse> {
+    let (tree_id, leaf_index) = read_leaf_index(deps.storage, commitment)?;
+    Ok(LeafIndexResponse {
+        tree_id,
+        leaf_index,
+    })
+}
+
+pub fn get_merkle_path(
+    deps: Deps,
+    tree_id: Option<u32>,
+    leaf_index: u32,
+) -> StdResult<MerklePathResponse> {
+    let vanchor = VANCHOR.load(deps.storage)?;
+    let tree_id = tree_id.unwrap_or(vanchor.merkle_tree.tree_id);
+    let (siblings, path_bits, root) = vanchor
+        .merkle_tree
+        .merkle_path(tree_id, leaf_index, deps.storage)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Ok(MerklePathResponse {
+        tree_id,
+        leaf_index,
+        root,
+        siblings,
+        path_bits,
+    })
+}
+
+pub fn get_is_known_nullifier(
+    deps: Deps,
+    nullifier: [u8; 32],
+) -> StdResult<IsKnownNullifierResponse> {
+    Ok(IsKnownNullifierResponse {
+        known: is_known_nullifier(deps.storage, nullifier),
+    })
+}
+
+pub fn get_spent_nullifiers(
+    deps: Deps,
+    nullifiers: Vec<[u8; 32]>,
+) -> StdResult<SpentNullifiersResponse> {
+    let spent = nullifiers
+        .into_iter()
+        .map(|nullifier| is_known_nullifier(deps.storage, nullifier))
+        .collect();
+    Ok(SpentNullifiersResponse { spent })
+}
+
+pub fn get_is_known_root(deps: Deps, root: [u8; 32]) -> StdResult<IsKnownRootResponse> {
+    let vanchor = VANCHOR.load(deps.storage)?;
+    Ok(IsKnownRootResponse {
+        known: vanchor.merkle_tree.is_known_root(root, deps.storage),
+    })
+}
+
+pub fn get_root_history(deps: Deps) -> StdResult<RootHistoryResponse> {
+    let vanchor = VANCHOR.load(deps.storage)?;
+    Ok(RootHistoryResponse {
+        roots: vanchor.merkle_tree.root_history(deps.storage),
+    })
+}
+
+pub fn get_is_known_neighbor_root(
+    deps: Deps,
+    chain_id: u64,
+    root: [u8; 32],
+) -> StdResult<IsKnownRootResponse> {
+    let vanchor = VANCHOR.load(deps.storage)?;
+    Ok(IsKnownRootResponse {
+        known: vanchor
+            .linkable_tree
+            .is_known_neighbor_root(chain_id, root, deps.storage),
+    })
+}
+
+pub fn get_neighbor_root_history(deps: Deps, chain_id: u64) -> StdResult<RootHistoryResponse> {
+    let vanchor = VANCHOR.load(deps.storage)?;
+    Ok(RootHistoryResponse {
+        roots: vanchor
+            .linkable_tree
+            .neighbor_root_history(chain_id, deps.storage),
+    })
+}
+
+pub fn get_public_key(deps: Deps, owner: String) -> StdResult<PublicKeyResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let public_key = PUBLIC_KEYS.load(deps.storage, owner.to_string())?;
+    Ok(PublicKeyResponse {
+        owner: owner.to_string(),
+        public_key,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // The pools deployed before the merkle node tracking have no nodes of the inserted leaves.
+    let vanchor = VANCHOR.load(deps.storage)?;
+    vanchor.merkle_tree.start_node_tracking(deps.storage)?;
     Ok(Response::default())
 }
@@ -19,7 +19,10 @@ use protocol_cosmwasm::token_wrapper::{
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Config {},
-    GetAmountToWrap { target_amount: String },
+    GetAmountToWrap {
+        target_amount: String,
+        token: Option<String>,
+    },
 }
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
@@ -72,16 +75,19 @@ impl WasmMockQuerier {
                 QueryMsg::Config {} => SystemResult::Ok(ContractResult::Ok(
                     to_binary(&TokenWrapperConfigResponse {
                         governor: "governor".to_string(),
+                        handler: "handler".to_string(),
                         native_token_denom: "uusd".to_string(),
                         fee_recipient: "fee-recipient".to_string(),
                         fee_percentage: "0.01".to_string(),
                         is_native_allowed: "true".to_string(),
                         wrapping_limit: "100".to_string(),
                         proposal_nonce: "0".to_string(),
+                        unwrap_fee_percentage: "0".to_string(),
+                        is_unwrap_fee_retained: "false".to_string(),
                     })
                     .unwrap(),
                 )),
-                QueryMsg::GetAmountToWrap { target_amount } => {
+                QueryMsg::GetAmountToWrap { target_amount, .. } => {
                     let targ_amt = Uint128::from_str(&target_amount).unwrap();
                     // Assumes that the "fee_percentage" is 10%
                     let amt_to_wrap = targ_amt.multiply_ratio(100_u128, 90_u128);
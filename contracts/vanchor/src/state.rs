@@ -1,11 +1,13 @@
-use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use protocol_cosmwasm::error::ContractError;
+use protocol_cosmwasm::ext_data::ExtDataEncoding;
+use protocol_cosmwasm::merkle_tree::{self, save_node};
 use protocol_cosmwasm::poseidon::Poseidon;
-use protocol_cosmwasm::structs::{ChainId, Edge, ROOT_HISTORY_SIZE};
+use protocol_cosmwasm::structs::{ChainId, Edge, HISTORY_LENGTH, ROOT_HISTORY_SIZE};
 use protocol_cosmwasm::vanchor_verifier::VAnchorVerifier;
 use protocol_cosmwasm::zeroes;
 
@@ -86,7 +88,7 @@ impl LinkableMerkleTree {
 
             let curr_neighbor_root_index =
                 read_curr_neighbor_root_index(store, edge.src_chain_id).unwrap_or_default();
-            let neighbor_root_index = curr_neighbor_root_index + 1 % ROOT_HISTORY_SIZE;
+            let neighbor_root_index = (curr_neighbor_root_index + 1) % HISTORY_LENGTH;
 
             save_curr_neighbor_root_index(store, edge.src_chain_id, neighbor_root_index)?;
             save_neighbor_roots(store, (edge.src_chain_id, neighbor_root_index), edge.root)?;
@@ -129,6 +131,24 @@ impl LinkableMerkleTree {
             .collect()
     }
 
+    /// Returns the known roots of the neighbor chain, from the latest to the oldest.
+    pub fn neighbor_root_history(&self, chain_id: ChainId, store: &dyn Storage) -> Vec<[u8; 32]> {
+        let mut roots: Vec<[u8; 32]> = vec![];
+        let mut i = read_curr_neighbor_root_index(store, chain_id).unwrap_or_default();
+        for _ in 0..HISTORY_LENGTH {
+            if let Ok(r) = read_neighbor_roots(store, (chain_id, i)) {
+                roots.push(r);
+            }
+
+            if i == 0 {
+                i = HISTORY_LENGTH - 1;
+            } else {
+                i -= 1;
+            }
+        }
+        roots
+    }
+
     pub fn is_known_neighbor_root(
         &self,
         chain_id: ChainId,
@@ -139,22 +159,7 @@ impl LinkableMerkleTree {
             return false;
         }
 
-        let mut i = read_curr_neighbor_root_index(store, chain_id).unwrap_or_default();
-        for _ in 0..ROOT_HISTORY_SIZE {
-            if let Ok(r) = read_neighbor_roots(store, (chain_id, i)) {
-                if r == root {
-                    return true;
-                }
-
-                if i == 0 {
-                    i = ROOT_HISTORY_SIZE - 1;
-                } else {
-                    i -= 1;
-                }
-            }
-        }
-
-        false
+        self.neighbor_root_history(chain_id, store).contains(&root)
     }
 
     pub fn is_valid_neighbor_roots(&self, roots: &[[u8; 32]], store: &dyn Storage) -> bool {
@@ -191,6 +196,7 @@ impl LinkableMerkleTree {
 ///    "max_fee"             Maximum `fee` amount in one transaction
 ///    "proposal_nonce"      Nonce value to track the proposals
 ///    "handler"             Address of `handler`(vanchor-handler), which updates the config of this contract(vanchor)
+///    "ext_data_encoding"   Layout of the `ext_data` hashing(Cosmos or EVM-compatible ABI)
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct VAnchor {
     pub creator: Addr,
@@ -203,6 +209,8 @@ pub struct VAnchor {
     pub max_fee: Uint128,
     pub proposal_nonce: u32,
     pub handler: Addr,
+    #[serde(default)]
+    pub ext_data_encoding: ExtDataEncoding,
 }
 
 pub const VANCHOR: Item<VAnchor> = Item::new("vanchor");
@@ -210,6 +218,9 @@ pub const VANCHOR: Item<VAnchor> = Item::new("vanchor");
 /// Struct to save the use of "nullifiers"
 pub const NULLIFIERS: Map<Vec<u8>, bool> = Map::new("used_nullifers");
 
+/// Shielded account "public keys" registered by the owners
+pub const PUBLIC_KEYS: Map<String, Vec<u8>> = Map::new("public_keys");
+
 /// "Poseidon hasher"
 pub const HASHER: Item<Poseidon> = Item::new("poseidon");
 
@@ -225,6 +236,9 @@ pub struct MerkleTree {
     pub levels: u32,
     pub current_root_index: u32,
     pub next_index: u32,
+    /// Id of the current tree, increased when the tree is full
+    #[serde(default)]
+    pub tree_id: u32,
 }
 
 /// MerkleTree "filled_subtrees"
@@ -249,6 +263,35 @@ pub fn read_root(store: &dyn Storage, k: u32) -> StdResult<[u8; 32]> {
     MERKLEROOTS.load(store, k.to_string())
 }
 
+/// MerkleTree leaf indices (commitment -> (tree_id, leaf_index))
+pub const LEAF_INDICES: Map<Vec<u8>, (u32, u32)> = Map::new("leaf_indices");
+
+/// Saves the (tree_id, leaf_index) of the leaf, rejecting the commitment already inserted into any tree,
+/// so that each commitment is mapped to a single (tree_id, leaf_index).
+/// The leaf itself is saved as the level 0 node of the tree.
+pub fn save_leaf_index(
+    store: &mut dyn Storage,
+    tree_id: u32,
+    k: u32,
+    data: &[u8; 32],
+) -> Result<(), ContractError> {
+    if LEAF_INDICES.has(store, data.to_vec()) {
+        return Err(ContractError::DuplicateCommitment);
+    }
+    LEAF_INDICES.save(store, data.to_vec(), &(tree_id, k))?;
+    Ok(())
+}
+
+/// Returns the leaf, which is the level 0 node of the tree
+pub fn read_leaf(store: &dyn Storage, tree_id: u32, k: u32) -> StdResult<[u8; 32]> {
+    merkle_tree::read_node(store, tree_id, 0, k)?.ok_or_else(|| StdError::not_found("leaf"))
+}
+
+/// Returns the (tree_id, leaf_index) of the `commitment`
+pub fn read_leaf_index(store: &dyn Storage, commitment: [u8; 32]) -> StdResult<(u32, u32)> {
+    LEAF_INDICES.load(store, commitment.to_vec())
+}
+
 impl MerkleTree {
     fn hash_left_right(
         &self,
@@ -266,18 +309,65 @@ impl MerkleTree {
         leaf: [u8; 32],
         store: &mut dyn Storage,
     ) -> Result<u32, ContractError> {
+        self.insert_subtree(hasher, &[leaf], store)
+    }
+
+    /// Inserts the power-of-two batch of `leaves`, which should be aligned to its size.
+    /// The subtree of the batch is hashed once, and then the path is walked up once,
+    /// which results in the same tree(& root) as inserting the leaves one by one.
+    /// Returns the index of the first inserted leaf.
+    pub fn insert_subtree(
+        &mut self,
+        hasher: Poseidon,
+        leaves: &[[u8; 32]],
+        store: &mut dyn Storage,
+    ) -> Result<u32, ContractError> {
+        let batch_size = leaves.len() as u32;
+        if !batch_size.is_power_of_two() || batch_size > 2u32.pow(self.levels as u32) {
+            return Err(ContractError::InvalidInsertionBatch);
+        }
+
+        // Open a new tree if the current one is full.
+        // The roots of the previous tree are kept in the root history,
+        // so the proofs against them are still valid until they get rotated out.
+        if self.next_index == 2u32.pow(self.levels as u32) {
+            self.tree_id += 1;
+            self.next_index = 0;
+        }
         let next_index = self.next_index;
-        assert!(
-            next_index != 2u32.pow(self.levels as u32),
-            "Merkle tree is full"
-        );
+        if next_index % batch_size != 0 {
+            return Err(ContractError::InvalidInsertionBatch);
+        }
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            save_leaf_index(store, self.tree_id, next_index + i as u32, leaf)?;
+        }
 
-        let mut current_index = next_index;
-        let mut current_level_hash = leaf;
+        // Hash the subtree of the batch.
+        // The last left node of each level is kept as "filled subtree",
+        // same as the one by one insertion.
+        let subtree_levels = batch_size.trailing_zeros();
+        let mut layer = leaves.to_vec();
+        for i in 0..subtree_levels {
+            let first_index = next_index >> i;
+            for (j, node) in layer.iter().enumerate() {
+                save_node(store, self.tree_id, i, first_index + j as u32, node)?;
+            }
+            save_subtree(store, i, &layer[layer.len() - 2])?;
+            layer = layer
+                .chunks(2)
+                .map(|pair| self.hash_left_right(hasher.clone(), pair[0], pair[1]))
+                .collect::<Result<Vec<[u8; 32]>, ContractError>>()?;
+        }
+
+        // Walk up the path from the root of the subtree.
+        let mut current_index = next_index >> subtree_levels;
+        let mut current_level_hash = layer[0];
         let mut left: [u8; 32];
         let mut right: [u8; 32];
 
-        for i in 0..self.levels {
+        for i in subtree_levels..self.levels {
+            save_node(store, self.tree_id, i, current_index, &current_level_hash)?;
             if current_index % 2 == 0 {
                 left = current_level_hash;
                 right = zeroes::zeroes(i);
@@ -290,11 +380,12 @@ impl MerkleTree {
             current_level_hash = self.hash_left_right(hasher.clone(), left, right)?;
             current_index /= 2;
         }
+        save_node(store, self.tree_id, self.levels, 0, &current_level_hash)?;
 
         let new_root_index = (self.current_root_index + 1) % ROOT_HISTORY_SIZE;
         self.current_root_index = new_root_index;
         save_root(store, new_root_index, &current_level_hash)?;
-        self.next_index = next_index + 1;
+        self.next_index = next_index + batch_size;
         Ok(next_index)
     }
 
@@ -319,4 +410,60 @@ impl MerkleTree {
 
         false
     }
+
+    /// Returns the known roots of the tree, from the latest to the oldest.
+    pub fn root_history(&self, store: &dyn Storage) -> Vec<[u8; 32]> {
+        let mut roots: Vec<[u8; 32]> = vec![];
+        let mut i = self.current_root_index;
+        for _ in 0..ROOT_HISTORY_SIZE {
+            if let Ok(r) = read_root(store, i) {
+                roots.push(r);
+            }
+
+            if i == 0 {
+                i = ROOT_HISTORY_SIZE - 1;
+            } else {
+                i -= 1;
+            }
+        }
+        roots
+    }
+
+    /// Returns the number of leaves in the tree of `tree_id`.
+    pub fn tree_size(&self, tree_id: u32) -> u32 {
+        match tree_id {
+            id if id < self.tree_id => 2u32.pow(self.levels as u32),
+            id if id == self.tree_id => self.next_index,
+            _ => 0,
+        }
+    }
+
+    /// Returns the merkle path of the leaf at `leaf_index` in the tree of `tree_id`,
+    /// from the nodes stored on insertion.
+    /// Returns the sibling hashes, path bits(`true` if the node is the right child) & root.
+    pub fn merkle_path(
+        &self,
+        tree_id: u32,
+        leaf_index: u32,
+        store: &dyn Storage,
+    ) -> Result<(Vec<[u8; 32]>, Vec<bool>, [u8; 32]), ContractError> {
+        if leaf_index >= self.tree_size(tree_id) {
+            return Err(ContractError::ItemNotFound);
+        }
+        merkle_tree::merkle_path(store, tree_id, self.levels, leaf_index)
+    }
+
+    /// Starts the tracking of merkle nodes for the tree filled before it, on migration.
+    /// The merkle paths of the earlier leaves are not available afterwards.
+    pub fn start_node_tracking(&self, store: &mut dyn Storage) -> StdResult<()> {
+        let root = read_root(store, self.current_root_index)?;
+        merkle_tree::start_node_tracking(
+            store,
+            self.tree_id,
+            self.levels,
+            self.next_index,
+            root,
+            read_subtree,
+        )
+    }
 }
@@ -2,15 +2,19 @@ use ark_ff::BigInteger;
 use ark_ff::PrimeField;
 use arkworks_setups::Curve;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockStorage};
-use cosmwasm_std::{attr, coins, to_binary, OwnedDeps, Uint128};
-use cw20::Cw20ReceiveMsg;
+use cosmwasm_std::{
+    attr, coins, from_binary, to_binary, Binary, CosmosMsg, OwnedDeps, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use protocol_cosmwasm::error::ContractError;
 use sp_core::hashing::keccak_256;
 
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, query};
+use protocol_cosmwasm::structs::{LeafIndexResponse, LeavesResponse};
 use protocol_cosmwasm::utils::compute_chain_id_type;
 use protocol_cosmwasm::vanchor::{
-    Cw20HookMsg, ExecuteMsg, ExtData, InstantiateMsg, ProofData, UpdateConfigMsg,
+    Cw20HookMsg, ExecuteMsg, ExtAmount, ExtData, InstantiateMsg, ProofData, PublicKeyResponse,
+    QueryMsg, UpdateConfigMsg,
 };
 use protocol_cosmwasm::zeroes::zeroes;
 
@@ -48,6 +52,7 @@ fn create_vanchor() -> OwnedDeps<MockStorage, MockApi, crate::mock_querier::Wasm
         max_fee: Uint128::from(MAX_FEE),
         tokenwrapper_addr: CW20_ADDRESS.to_string(),
         handler: HANDLER.to_string(),
+        ext_data_encoding: None,
     };
     let info = mock_info("creator", &[]);
 
@@ -69,6 +74,11 @@ fn hash_ext_data(ext_data: ExtData, ext_amount: i128, fee: u128) -> [u8; 32] {
     ext_data_args.extend_from_slice(&fee_bytes);
     ext_data_args.extend_from_slice(&ext_data.encrypted_output1);
     ext_data_args.extend_from_slice(&ext_data.encrypted_output2);
+    if let Some(hook) = ext_data.hook {
+        ext_data_args.extend_from_slice(&element_encoder(&[1]));
+        ext_data_args.extend_from_slice(&element_encoder(&(hook.len() as u64).to_le_bytes()));
+        ext_data_args.extend_from_slice(hook.as_slice());
+    }
 
     keccak_256(&ext_data_args)
 }
@@ -86,6 +96,7 @@ fn test_vanchor_proper_initialization() {
         max_fee: Uint128::from(MAX_FEE),
         tokenwrapper_addr: CW20_ADDRESS.to_string(),
         handler: HANDLER.to_string(),
+        ext_data_encoding: None,
     };
     let info = mock_info("creator", &[]);
 
@@ -102,6 +113,7 @@ fn test_vanchor_update_config() {
     let update_config_msg = UpdateConfigMsg {
         max_ext_amt: Some(Uint128::from(1u128)),
         max_fee: Some(Uint128::from(1u128)),
+        ext_data_encoding: None,
     };
     let info = mock_info("intruder", &[]);
     assert!(
@@ -119,6 +131,7 @@ fn test_vanchor_update_config() {
     let update_config_msg = UpdateConfigMsg {
         max_ext_amt: Some(Uint128::from(1u128)),
         max_fee: Some(Uint128::from(1u128)),
+        ext_data_encoding: None,
     };
     let info = mock_info("creator", &[]);
     let _ = execute(
@@ -161,10 +174,11 @@ fn test_vanchor_should_complete_2x2_transaction_with_deposit_cw20() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -210,7 +224,47 @@ fn test_vanchor_should_complete_2x2_transaction_with_deposit_cw20() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 5);
+
+    // Every output commitment & input nullifier has its own event.
+    let event_types: Vec<&str> = response.events.iter().map(|e| e.ty.as_str()).collect();
+    assert_eq!(
+        event_types,
+        vec![
+            "vanchor-deposit",
+            "vanchor-commitment",
+            "vanchor-commitment",
+            "vanchor-nullifier",
+            "vanchor-nullifier",
+        ]
+    );
+    assert_eq!(response.events[1].attributes[1], attr("leaf_index", "0"));
+    assert_eq!(response.events[2].attributes[1], attr("leaf_index", "1"));
+
+    // Output commitments should be stored as "leaves".
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: None,
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.leaves.len(), 2);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LeafIndex {
+            commitment: leaves_response.leaves[1],
+        },
+    )
+    .unwrap();
+    let leaf_index_response: LeafIndexResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaf_index_response.leaf_index, 1);
 }
 
 #[test]
@@ -244,10 +298,11 @@ fn test_vanchor_should_complete_2x2_transaction_with_withdraw_cw20() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -317,10 +372,11 @@ fn test_vanchor_should_complete_2x2_transaction_with_withdraw_cw20() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -362,7 +418,174 @@ fn test_vanchor_should_complete_2x2_transaction_with_withdraw_cw20() {
 
     // Withdraw "7" cw20 tokens.
     let response = execute(deps.as_mut(), mock_env(), info, withdraw_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 5);
+}
+
+#[test]
+fn test_vanchor_should_complete_2x2_transaction_with_withdraw_cw20_hook() {
+    // Instantiate the "vanchor" contract.
+    let mut deps = create_vanchor();
+
+    // Initialize the vanchor
+    let (pk_bytes, _) = crate::test_util::setup_environment_2_2_2(Curve::Bn254);
+    let ext_amount = 10_i128;
+    let fee = 0_u128;
+
+    let public_amount = 10_i128;
+
+    let chain_type = [4, 0];
+    let chain_id = compute_chain_id_type(CHAIN_ID, &chain_type);
+    let in_chain_ids = [chain_id; 2];
+    let in_amounts = [0, 0];
+    let in_indices = [0, 1];
+    let out_chain_ids = [chain_id; 2];
+    let out_amounts = [10, 0];
+
+    let in_utxos = crate::test_util::setup_utxos_2_2_2(in_chain_ids, in_amounts, Some(in_indices));
+    // We are adding indices to out utxos, since they will be used as an input utxos in next transaction
+    let out_utxos =
+        crate::test_util::setup_utxos_2_2_2(out_chain_ids, out_amounts, Some(in_indices));
+
+    let output1 = out_utxos[0].commitment.into_repr().to_bytes_le();
+    let output2 = out_utxos[1].commitment.into_repr().to_bytes_le();
+
+    let ext_data = ExtData {
+        recipient: RECIPIENT.to_string(),
+        relayer: RELAYER.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
+        fee: Uint128::from(fee),
+        encrypted_output1: output1,
+        encrypted_output2: output2,
+        hook: None,
+    };
+
+    let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
+
+    let custom_roots = Some([zeroes(LEVELS), zeroes(LEVELS)].map(|x| x.to_vec()));
+    let (proof, public_inputs) = crate::test_util::setup_zk_circuit_2_2_2(
+        public_amount,
+        chain_id,
+        ext_data_hash.to_vec(),
+        in_utxos,
+        out_utxos.clone(),
+        custom_roots,
+        pk_bytes,
+    );
+
+    // Deconstructing public inputs
+    let (_chain_id, public_amount, root_set, nullifiers, commitments, ext_data_hash) =
+        crate::test_util::deconstruct_public_inputs_el_2_2_2(&public_inputs);
+
+    // Constructing proof data
+    let root_set = root_set.into_iter().map(|v| v.0).collect();
+    let nullifiers = nullifiers.into_iter().map(|v| v.0).collect();
+    let commitments = commitments.into_iter().map(|v| v.0).collect();
+    let proof_data = ProofData::new(
+        proof,
+        public_amount.0,
+        root_set,
+        nullifiers,
+        commitments,
+        ext_data_hash.0,
+    );
+
+    // Should "transact" with success.
+    let info = mock_info(CW20_ADDRESS, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TRANSACTOR.to_string(),
+        amount: Uint128::from(10_u128),
+        msg: to_binary(&Cw20HookMsg::TransactDeposit {
+            proof_data: proof_data,
+            ext_data: ext_data,
+        })
+        .unwrap(),
+    });
+
+    // Deposit "10" cw20 tokens.
+    let _ = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
+
+    // Prepare the "withdraw" data.
+    let (pk_bytes, _) = crate::test_util::setup_environment_2_2_2(Curve::Bn254);
+
+    let ext_amount = -5_i128;
+    let fee = 2_u128;
+    let hook = to_binary(&"deposit into lending pool".to_string()).unwrap();
+
+    let public_amount = -7_i128;
+
+    let chain_id = compute_chain_id_type(CHAIN_ID, &CHAIN_TYPE);
+    let out_chain_ids = [CHAIN_ID; 2];
+    // After withdrawing -7
+    let out_amounts = [1, 2];
+
+    // "in_utxos" become the "out_utxos" of last transact.
+    let in_utxos = out_utxos;
+    let out_utxos = crate::test_util::setup_utxos_2_2_2(out_chain_ids, out_amounts, None);
+
+    let output1 = out_utxos[0].commitment.into_repr().to_bytes_le();
+    let output2 = out_utxos[1].commitment.into_repr().to_bytes_le();
+    let ext_data = ExtData {
+        recipient: RECIPIENT.to_string(),
+        relayer: RELAYER.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
+        fee: Uint128::from(fee),
+        encrypted_output1: output1,
+        encrypted_output2: output2,
+        hook: Some(hook.clone()),
+    };
+
+    let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
+
+    let (proof, public_inputs) = crate::test_util::setup_zk_circuit_2_2_2(
+        public_amount,
+        chain_id,
+        ext_data_hash.to_vec(),
+        in_utxos,
+        out_utxos,
+        None,
+        pk_bytes,
+    );
+
+    // Deconstructing public inputs
+    let (_chain_id, public_amount, root_set, nullifiers, commitments, ext_data_hash) =
+        crate::test_util::deconstruct_public_inputs_el_2_2_2(&public_inputs);
+
+    // Constructing proof data
+    let root_set = root_set.into_iter().map(|v| v.0).collect();
+    let nullifiers = nullifiers.into_iter().map(|v| v.0).collect();
+    let commitments = commitments.into_iter().map(|v| v.0).collect();
+
+    let proof_data = ProofData::new(
+        proof,
+        public_amount.0,
+        root_set,
+        nullifiers,
+        commitments,
+        ext_data_hash.0,
+    );
+
+    // Should "transact" with success.
+    let info = mock_info(CW20_ADDRESS, &[]);
+    let withdraw_cw20_msg = ExecuteMsg::TransactWithdraw {
+        proof_data: proof_data,
+        ext_data: ext_data,
+    };
+
+    // Withdraw "7" cw20 tokens, delivering "5" tokens to the "recipient" contract with the hook.
+    let response = execute(deps.as_mut(), mock_env(), info, withdraw_cw20_msg).unwrap();
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: CW20_ADDRESS.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: RECIPIENT.to_string(),
+                amount: Uint128::from(5_u128),
+                msg: hook,
+            })
+            .unwrap(),
+        })
+    );
 }
 
 #[test]
@@ -396,10 +619,11 @@ fn test_vanchor_should_not_complete_transaction_if_ext_data_is_invalid() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -469,10 +693,11 @@ fn test_vanchor_should_not_complete_transaction_if_ext_data_is_invalid() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1.clone(),
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -495,10 +720,11 @@ fn test_vanchor_should_not_complete_transaction_if_ext_data_is_invalid() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: vec![0u8; 32],
+        hook: None,
     };
 
     // Constructing proof data
@@ -557,10 +783,11 @@ fn test_vanchor_should_not_complete_withdraw_if_out_amount_sum_is_too_big() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -630,10 +857,11 @@ fn test_vanchor_should_not_complete_withdraw_if_out_amount_sum_is_too_big() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -708,10 +936,11 @@ fn test_vanchor_should_not_complete_withdraw_if_out_amount_sum_is_too_small() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -781,10 +1010,11 @@ fn test_vanchor_should_not_complete_withdraw_if_out_amount_sum_is_too_small() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -859,10 +1089,11 @@ fn test_vanchor_should_not_be_able_to_double_spend() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -932,10 +1163,11 @@ fn test_vanchor_should_not_be_able_to_double_spend() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -1023,10 +1255,11 @@ fn test_vanchor_should_complete_16x2_transaction_with_deposit_cw20() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -1072,7 +1305,7 @@ fn test_vanchor_should_complete_16x2_transaction_with_deposit_cw20() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 19);
 }
 
 #[test]
@@ -1106,10 +1339,11 @@ fn test_vanchor_should_complete_16x2_transaction_with_withdraw_cw20() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -1179,10 +1413,11 @@ fn test_vanchor_should_complete_16x2_transaction_with_withdraw_cw20() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -1224,7 +1459,7 @@ fn test_vanchor_should_complete_16x2_transaction_with_withdraw_cw20() {
 
     // Withdraw "7" cw20 tokens.
     let response = execute(deps.as_mut(), mock_env(), info, withdraw_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 5);
 }
 
 #[test]
@@ -1357,10 +1592,11 @@ fn test_vanchor_wrap_and_deposit_cw20() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -1406,7 +1642,7 @@ fn test_vanchor_wrap_and_deposit_cw20() {
     });
 
     let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 5);
     assert_eq!(response.messages.len(), 1);
 }
 
@@ -1441,10 +1677,11 @@ fn test_vanchor_withdraw_and_unwrap_native() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -1514,10 +1751,11 @@ fn test_vanchor_withdraw_and_unwrap_native() {
     let ext_data = ExtData {
         recipient: RECIPIENT.to_string(),
         relayer: RELAYER.to_string(),
-        ext_amount: ext_amount.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
         fee: Uint128::from(fee),
         encrypted_output1: output1,
         encrypted_output2: output2,
+        hook: None,
     };
 
     let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
@@ -1560,7 +1798,7 @@ fn test_vanchor_withdraw_and_unwrap_native() {
 
     // Withdraw "7" cw20 tokens.
     let response = execute(deps.as_mut(), mock_env(), info, withdraw_cw20_msg).unwrap();
-    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events.len(), 5);
 }
 
 #[test]
@@ -1604,3 +1842,599 @@ fn test_vanchor_set_handler() {
         ]
     );
 }
+
+#[test]
+fn test_vanchor_subtree_insertion_matches_sequential_insertion() {
+    use crate::state::{read_leaf, read_root, read_subtree, save_root, save_subtree, MerkleTree};
+    use protocol_cosmwasm::poseidon::Poseidon;
+
+    const TREE_LEVELS: u32 = 3;
+
+    fn new_tree(store: &mut MockStorage) -> MerkleTree {
+        for i in 0..TREE_LEVELS {
+            save_subtree(store, i, &zeroes(i)).unwrap();
+        }
+        save_root(store, 0, &zeroes(TREE_LEVELS)).unwrap();
+        MerkleTree {
+            levels: TREE_LEVELS,
+            current_root_index: 0,
+            next_index: 0,
+            tree_id: 0,
+        }
+    }
+
+    let leaves: Vec<[u8; 32]> = (1..=8u8).map(|i| element_encoder(&[i])).collect();
+
+    for batch_size in [2, 4, 8] {
+        let mut seq_store = MockStorage::new();
+        let mut seq_tree = new_tree(&mut seq_store);
+        let mut batch_store = MockStorage::new();
+        let mut batch_tree = new_tree(&mut batch_store);
+
+        for batch in leaves.chunks(batch_size) {
+            for leaf in batch {
+                seq_tree
+                    .insert(Poseidon::new(), *leaf, &mut seq_store)
+                    .unwrap();
+            }
+            let first_index = batch_tree
+                .insert_subtree(Poseidon::new(), batch, &mut batch_store)
+                .unwrap();
+            assert_eq!(first_index + batch.len() as u32, seq_tree.next_index);
+
+            // Roots & "filled subtrees" should be the same after each batch
+            assert_eq!(batch_tree.next_index, seq_tree.next_index);
+            assert_eq!(
+                read_root(&batch_store, batch_tree.current_root_index).unwrap(),
+                read_root(&seq_store, seq_tree.current_root_index).unwrap(),
+            );
+            for i in 0..TREE_LEVELS {
+                assert_eq!(
+                    read_subtree(&batch_store, i).unwrap(),
+                    read_subtree(&seq_store, i).unwrap(),
+                );
+            }
+
+            // Merkle paths should be the same & lead to the current root
+            for index in 0..batch_tree.next_index {
+                let path = batch_tree.merkle_path(0, index, &batch_store).unwrap();
+                assert_eq!(path, seq_tree.merkle_path(0, index, &seq_store).unwrap());
+
+                let (siblings, path_bits, root) = path;
+                let mut node = leaves[index as usize];
+                for (sibling, is_right) in siblings.iter().zip(path_bits) {
+                    let inputs = if is_right {
+                        vec![*sibling, node]
+                    } else {
+                        vec![node, *sibling]
+                    };
+                    node = Poseidon::new().hash(inputs).unwrap();
+                }
+                assert_eq!(node, root);
+                assert_eq!(
+                    root,
+                    read_root(&batch_store, batch_tree.current_root_index).unwrap()
+                );
+            }
+        }
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert_eq!(read_leaf(&batch_store, 0, i as u32).unwrap(), *leaf);
+        }
+    }
+
+    // Fails to insert the batch which is not aligned to its size
+    let mut store = MockStorage::new();
+    let mut tree = new_tree(&mut store);
+    tree.insert(Poseidon::new(), leaves[0], &mut store).unwrap();
+    let err = tree
+        .insert_subtree(Poseidon::new(), &leaves[1..3], &mut store)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInsertionBatch);
+
+    // Fails to insert the batch whose size is not a power of two
+    let mut store = MockStorage::new();
+    let mut tree = new_tree(&mut store);
+    let err = tree
+        .insert_subtree(Poseidon::new(), &leaves[0..3], &mut store)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInsertionBatch);
+}
+
+#[test]
+fn test_vanchor_subtree_insertion_opens_new_tree_when_full() {
+    use crate::state::{read_leaf_index, read_root, save_root, save_subtree, MerkleTree};
+    use protocol_cosmwasm::poseidon::Poseidon;
+
+    const TREE_LEVELS: u32 = 2;
+
+    let mut store = MockStorage::new();
+    for i in 0..TREE_LEVELS {
+        save_subtree(&mut store, i, &zeroes(i)).unwrap();
+    }
+    save_root(&mut store, 0, &zeroes(TREE_LEVELS)).unwrap();
+    let mut tree = MerkleTree {
+        levels: TREE_LEVELS,
+        current_root_index: 0,
+        next_index: 0,
+        tree_id: 0,
+    };
+
+    let leaves: Vec<[u8; 32]> = (1..=12u8).map(|i| element_encoder(&[i])).collect();
+
+    // The batch landing on the end of tree fills it up
+    tree.insert_subtree(Poseidon::new(), &leaves[0..2], &mut store)
+        .unwrap();
+    assert_eq!(
+        tree.insert_subtree(Poseidon::new(), &leaves[2..4], &mut store)
+            .unwrap(),
+        2
+    );
+    assert_eq!((tree.tree_id, tree.next_index), (0, 4));
+    let full_tree_root = read_root(&store, tree.current_root_index).unwrap();
+
+    // The next batch opens a new tree
+    assert_eq!(
+        tree.insert_subtree(Poseidon::new(), &leaves[4..6], &mut store)
+            .unwrap(),
+        0
+    );
+    assert_eq!((tree.tree_id, tree.next_index), (1, 2));
+    assert_eq!(read_leaf_index(&store, leaves[4]).unwrap(), (1, 0));
+    assert!(tree.is_known_root(full_tree_root, &store));
+    assert_eq!(tree.merkle_path(0, 3, &store).unwrap().2, full_tree_root);
+
+    // Fails to insert the batch crossing the end of tree
+    tree.insert(Poseidon::new(), leaves[6], &mut store).unwrap();
+    let err = tree
+        .insert_subtree(Poseidon::new(), &leaves[7..9], &mut store)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInsertionBatch);
+    let err = tree
+        .insert_subtree(Poseidon::new(), &leaves[7..11], &mut store)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInsertionBatch);
+
+    // The full tree size batch lands on the new tree, after filling the current one
+    tree.insert(Poseidon::new(), leaves[7], &mut store).unwrap();
+    assert_eq!((tree.tree_id, tree.next_index), (1, 4));
+    assert_eq!(
+        tree.insert_subtree(Poseidon::new(), &leaves[8..12], &mut store)
+            .unwrap(),
+        0
+    );
+    assert_eq!((tree.tree_id, tree.next_index), (2, 4));
+    assert_eq!(read_leaf_index(&store, leaves[11]).unwrap(), (2, 3));
+}
+
+#[test]
+fn test_vanchor_ext_amount_serde() {
+    let ext_data = ExtData {
+        recipient: RECIPIENT.to_string(),
+        relayer: RELAYER.to_string(),
+        ext_amount: ExtAmount::new(-5),
+        fee: Uint128::from(2_u128),
+        encrypted_output1: vec![],
+        encrypted_output2: vec![],
+        hook: None,
+    };
+    let json = to_binary(&ext_data).unwrap();
+    assert!(String::from_utf8(json.to_vec())
+        .unwrap()
+        .contains(r#""ext_amount":"-5""#));
+    let decoded: ExtData = from_binary(&json).unwrap();
+    assert_eq!(decoded, ext_data);
+    assert!(decoded.ext_amount.is_withdraw());
+    assert_eq!(decoded.ext_amount.unsigned_abs(), Uint128::from(5_u128));
+
+    // Malformed "ext_amount" is rejected instead of aborting the contract
+    for ext_amount in [r#""10a""#, r#""""#, "10"] {
+        let json = format!(
+            r#"{{"recipient":"{}","relayer":"{}","ext_amount":{},"fee":"0","encrypted_output1":[],"encrypted_output2":[]}}"#,
+            RECIPIENT, RELAYER, ext_amount
+        );
+        assert!(cosmwasm_std::from_slice::<ExtData>(json.as_bytes()).is_err());
+    }
+}
+
+#[test]
+fn test_vanchor_ext_data_hash_vectors() {
+    use protocol_cosmwasm::ext_data::{
+        abi_encode_ext_data, hash_abi_ext_data, hash_cosmos_ext_data,
+    };
+
+    let ext_data = ExtData {
+        recipient: RECIPIENT.to_string(),
+        relayer: RELAYER.to_string(),
+        ext_amount: ExtAmount::new(-5),
+        fee: Uint128::from(2_u128),
+        encrypted_output1: vec![1u8; 40],
+        encrypted_output2: vec![2u8; 3],
+        hook: None,
+    };
+
+    // "Cosmos" layout
+    assert_eq!(
+        hex::encode(hash_cosmos_ext_data(&ext_data).unwrap()),
+        "d5ea9b45776e24c62937e9d3a3fbb3c8a9a94ea1913b2f09390b7d21bcfa701c"
+    );
+
+    // "Abi" layout, same as `abi.encode(extData)` of the EVM VAnchor
+    let recipient = [0x11u8; 20];
+    let relayer = [0x22u8; 20];
+    let encoded = abi_encode_ext_data(&ext_data, &recipient, &relayer).unwrap();
+    let expected_words = [
+        "0000000000000000000000000000000000000000000000000000000000000020", // offset of tuple
+        "0000000000000000000000001111111111111111111111111111111111111111", // recipient
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffb", // extAmount(-5)
+        "0000000000000000000000002222222222222222222222222222222222222222", // relayer
+        "0000000000000000000000000000000000000000000000000000000000000002", // fee
+        "00000000000000000000000000000000000000000000000000000000000000c0", // offset of encryptedOutput1
+        "0000000000000000000000000000000000000000000000000000000000000120", // offset of encryptedOutput2
+        "0000000000000000000000000000000000000000000000000000000000000028", // encryptedOutput1
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        "0101010101010101000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000003", // encryptedOutput2
+        "0202020000000000000000000000000000000000000000000000000000000000",
+    ];
+    assert_eq!(hex::encode(&encoded), expected_words.concat());
+    assert_eq!(
+        hex::encode(hash_abi_ext_data(&ext_data, &recipient, &relayer).unwrap()),
+        "344712f2f761d4ce07ae8e066ee945d6416f5ca192b80e7e2c251b6626899d04"
+    );
+
+    // Fails with the address longer than ABI word
+    let err = hash_abi_ext_data(&ext_data, &[0u8; 33], &relayer).unwrap_err();
+    assert_eq!(err, ContractError::InvalidExtData);
+
+    // "hook" is bound into the hash as the last "bytes" value
+    let hooked_ext_data = ExtData {
+        hook: Some(vec![3u8; 5].into()),
+        ..ext_data.clone()
+    };
+    let encoded = abi_encode_ext_data(&hooked_ext_data, &recipient, &relayer).unwrap();
+    let expected_words = [
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        "0000000000000000000000001111111111111111111111111111111111111111",
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffb",
+        "0000000000000000000000002222222222222222222222222222222222222222",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+        "00000000000000000000000000000000000000000000000000000000000000e0",
+        "0000000000000000000000000000000000000000000000000000000000000140",
+        "0000000000000000000000000000000000000000000000000000000000000180", // offset of hook
+        "0000000000000000000000000000000000000000000000000000000000000028",
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        "0101010101010101000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "0202020000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000005", // hook
+        "0303030303000000000000000000000000000000000000000000000000000000",
+    ];
+    assert_eq!(hex::encode(&encoded), expected_words.concat());
+    assert_eq!(
+        hex::encode(hash_abi_ext_data(&hooked_ext_data, &recipient, &relayer).unwrap()),
+        "6d38215c2e2f089110ff94d3e2b26fb7fcdf2aa07b24ddf301627beb176dd117"
+    );
+    assert_eq!(
+        hex::encode(hash_cosmos_ext_data(&hooked_ext_data).unwrap()),
+        "2c2a1f6093048885fb1021388e6bc8e5eca7abb3c92a769f72a6d6fdfcc6b62b"
+    );
+}
+
+#[test]
+fn test_vanchor_default_ext_data_hash_matches_baseline() {
+    use protocol_cosmwasm::ext_data::{self, ExtDataEncoding};
+
+    // Same "ext_data" & "ext_data_hash" as the e2e test(test-scripts/src/processes/tests/vanchor.ts)
+    let deposit_ext_data = ExtData {
+        recipient: RECIPIENT.to_string(),
+        relayer: RELAYER.to_string(),
+        ext_amount: ExtAmount::new(10),
+        fee: Uint128::zero(),
+        encrypted_output1: vec![
+            216, 73, 17, 34, 172, 162, 84, 217, 129, 71, 47, 175, 98, 219, 71, 239, 23, 33, 110, 1,
+            177, 103, 163, 207, 70, 194, 3, 92, 25, 127, 11, 26,
+        ],
+        encrypted_output2: vec![
+            153, 86, 203, 54, 30, 5, 223, 254, 187, 84, 67, 253, 65, 180, 17, 212, 93, 47, 31, 139,
+            88, 128, 180, 195, 54, 35, 96, 56, 135, 158, 246, 38,
+        ],
+        hook: None,
+    };
+    let withdraw_ext_data = ExtData {
+        recipient: RECIPIENT.to_string(),
+        relayer: RELAYER.to_string(),
+        ext_amount: ExtAmount::new(-5),
+        fee: Uint128::from(2_u128),
+        encrypted_output1: vec![
+            98, 238, 231, 144, 183, 99, 173, 197, 28, 209, 251, 163, 153, 17, 59, 23, 90, 133, 32,
+            215, 197, 188, 53, 189, 126, 253, 32, 20, 88, 91, 57, 12,
+        ],
+        encrypted_output2: vec![
+            72, 212, 77, 107, 179, 142, 41, 140, 75, 67, 54, 149, 28, 254, 228, 90, 185, 103, 227,
+            182, 120, 174, 149, 183, 211, 130, 200, 21, 32, 97, 90, 35,
+        ],
+        hook: None,
+    };
+
+    let api = MockApi::default();
+    assert_eq!(
+        ext_data::hash_ext_data(&api, &deposit_ext_data, ExtDataEncoding::default()).unwrap(),
+        [
+            24, 235, 82, 104, 131, 174, 191, 31, 172, 125, 168, 38, 125, 31, 19, 53, 145, 85, 232,
+            24, 129, 192, 12, 158, 128, 29, 114, 26, 73, 49, 159, 34
+        ]
+    );
+    assert_eq!(
+        ext_data::hash_ext_data(&api, &withdraw_ext_data, ExtDataEncoding::default()).unwrap(),
+        [
+            158, 129, 108, 189, 27, 52, 160, 17, 50, 97, 24, 3, 37, 155, 173, 187, 15, 14, 247,
+            247, 185, 191, 146, 162, 49, 47, 191, 6, 131, 144, 79, 48
+        ]
+    );
+}
+
+#[test]
+fn test_vanchor_cosmos_ext_data_hash_binds_hook() {
+    use protocol_cosmwasm::ext_data::hash_cosmos_ext_data;
+
+    let ext_data = ExtData {
+        recipient: RECIPIENT.to_string(),
+        relayer: RELAYER.to_string(),
+        ext_amount: ExtAmount::new(-5),
+        fee: Uint128::from(2_u128),
+        encrypted_output1: vec![1u8; 4],
+        encrypted_output2: vec![2u8; 4],
+        hook: Some(vec![3u8; 4].into()),
+    };
+    let hash = hash_cosmos_ext_data(&ext_data).unwrap();
+
+    // Same concatenated bytes, split at the different points around the "hook"
+    let split_variants = [
+        ExtData {
+            encrypted_output2: [vec![2u8; 4], vec![3u8; 4]].concat(),
+            hook: Some(Binary::default()),
+            ..ext_data.clone()
+        },
+        ExtData {
+            encrypted_output2: [vec![2u8; 4], vec![3u8; 4]].concat(),
+            hook: None,
+            ..ext_data.clone()
+        },
+        ExtData {
+            encrypted_output2: vec![2u8; 2],
+            hook: Some([vec![2u8; 2], vec![3u8; 4]].concat().into()),
+            ..ext_data.clone()
+        },
+    ];
+    for variant in split_variants.iter() {
+        assert_ne!(hash_cosmos_ext_data(variant).unwrap(), hash);
+    }
+
+    // Empty "hook" differs from no "hook"
+    assert_ne!(
+        hash_cosmos_ext_data(&ExtData {
+            hook: Some(Binary::default()),
+            ..ext_data.clone()
+        })
+        .unwrap(),
+        hash_cosmos_ext_data(&ExtData {
+            hook: None,
+            ..ext_data
+        })
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_vanchor_register_and_transact_deposit() {
+    // Instantiate the "vanchor" contract.
+    let mut deps = create_vanchor();
+
+    // Initialize the vanchor
+    let (pk_bytes, _) = crate::test_util::setup_environment_2_2_2(Curve::Bn254);
+    let ext_amount = 10_i128;
+    let fee = 0_u128;
+
+    let public_amount = 10_i128;
+
+    let chain_type = [4, 0];
+    let chain_id = compute_chain_id_type(CHAIN_ID, &chain_type);
+    let in_chain_ids = [chain_id; 2];
+    let in_amounts = [0, 0];
+    let in_indices = [0, 1];
+    let out_chain_ids = [chain_id; 2];
+    let out_amounts = [10, 0];
+
+    let in_utxos = crate::test_util::setup_utxos_2_2_2(in_chain_ids, in_amounts, Some(in_indices));
+    // We are adding indices to out utxos, since they will be used as an input utxos in next transaction
+    let out_utxos =
+        crate::test_util::setup_utxos_2_2_2(out_chain_ids, out_amounts, Some(in_indices));
+
+    let output1 = out_utxos[0].commitment.into_repr().to_bytes_le();
+    let output2 = out_utxos[1].commitment.into_repr().to_bytes_le();
+
+    let ext_data = ExtData {
+        recipient: RECIPIENT.to_string(),
+        relayer: RELAYER.to_string(),
+        ext_amount: ExtAmount::new(ext_amount),
+        fee: Uint128::from(fee),
+        encrypted_output1: output1,
+        encrypted_output2: output2,
+        hook: None,
+    };
+
+    let ext_data_hash = hash_ext_data(ext_data.clone(), ext_amount, fee);
+
+    let custom_roots = Some([zeroes(LEVELS), zeroes(LEVELS)].map(|x| x.to_vec()));
+    let (proof, public_inputs) = crate::test_util::setup_zk_circuit_2_2_2(
+        public_amount,
+        chain_id,
+        ext_data_hash.to_vec(),
+        in_utxos,
+        out_utxos,
+        custom_roots,
+        pk_bytes,
+    );
+
+    // Deconstructing public inputs
+    let (_chain_id, public_amount, root_set, nullifiers, commitments, ext_data_hash) =
+        crate::test_util::deconstruct_public_inputs_el_2_2_2(&public_inputs);
+
+    // Constructing proof data
+    let root_set = root_set.into_iter().map(|v| v.0).collect();
+    let nullifiers = nullifiers.into_iter().map(|v| v.0).collect();
+    let commitments = commitments.into_iter().map(|v| v.0).collect();
+    let proof_data = ProofData::new(
+        proof,
+        public_amount.0,
+        root_set,
+        nullifiers,
+        commitments,
+        ext_data_hash.0,
+    );
+
+    // Should "register" & "transact" in one message.
+    let public_key = vec![7u8; 64];
+    let info = mock_info(CW20_ADDRESS, &[]);
+    let deposit_cw20_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TRANSACTOR.to_string(),
+        amount: Uint128::from(10_u128),
+        msg: to_binary(&Cw20HookMsg::RegisterAndTransactDeposit {
+            owner: TRANSACTOR.to_string(),
+            public_key: public_key.clone(),
+            proof_data,
+            ext_data,
+        })
+        .unwrap(),
+    });
+    let response = execute(deps.as_mut(), mock_env(), info, deposit_cw20_msg).unwrap();
+
+    // Both the "register" & "transact" attributes are kept.
+    assert_eq!(response.attributes, vec![attr("action", "register")]);
+    let event_types: Vec<&str> = response.events.iter().map(|e| e.ty.as_str()).collect();
+    assert_eq!(
+        event_types,
+        vec![
+            "vanchor-deposit",
+            "vanchor-commitment",
+            "vanchor-commitment",
+            "vanchor-nullifier",
+            "vanchor-nullifier",
+            "vanchor-public-key",
+        ]
+    );
+    assert_eq!(
+        response.events[0].attributes[..2],
+        [
+            attr("action", "transact_deposit"),
+            attr("ext_amt", ext_amount.to_string()),
+        ]
+    );
+    assert_eq!(
+        response.events[5].attributes,
+        vec![
+            attr("owner", TRANSACTOR),
+            attr("public_key", format!("0x{}", hex::encode(&public_key))),
+        ]
+    );
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::PublicKey {
+            owner: TRANSACTOR.to_string(),
+        },
+    )
+    .unwrap();
+    let resp: PublicKeyResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(resp.public_key, public_key);
+
+    let query_bin = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Leaves {
+            tree_id: None,
+            start_index: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let leaves_response: LeavesResponse = from_binary(&query_bin).unwrap();
+    assert_eq!(leaves_response.leaves.len(), 2);
+}
+
+#[test]
+fn test_vanchor_register() {
+    let mut deps = create_vanchor();
+    let public_key = vec![7u8; 32];
+
+    // Fails to "register" if tx sender is not the "owner"
+    let info = mock_info("anyone", &[]);
+    let register_msg = ExecuteMsg::Register {
+        owner: TRANSACTOR.to_string(),
+        public_key: public_key.clone(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, register_msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fails to "register" the empty public key
+    let info = mock_info(TRANSACTOR, &[]);
+    let register_msg = ExecuteMsg::Register {
+        owner: TRANSACTOR.to_string(),
+        public_key: vec![],
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, register_msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPublicKey);
+
+    // Fails to "register" the public key of invalid length
+    for len in [31, 33, 65] {
+        let info = mock_info(TRANSACTOR, &[]);
+        let register_msg = ExecuteMsg::Register {
+            owner: TRANSACTOR.to_string(),
+            public_key: vec![7u8; len],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, register_msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPublicKey);
+    }
+
+    // Succeed to "register"
+    let info = mock_info(TRANSACTOR, &[]);
+    let register_msg = ExecuteMsg::Register {
+        owner: TRANSACTOR.to_string(),
+        public_key: public_key.clone(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, register_msg).unwrap();
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].ty, "vanchor-public-key");
+    assert_eq!(
+        res.events[0].attributes,
+        vec![
+            attr("owner", TRANSACTOR),
+            attr("public_key", format!("0x{}", hex::encode(&public_key))),
+        ]
+    );
+
+    // Check the registered public key
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::PublicKey {
+            owner: TRANSACTOR.to_string(),
+        },
+    )
+    .unwrap();
+    let resp: PublicKeyResponse = from_binary(&res).unwrap();
+    assert_eq!(resp.owner, TRANSACTOR.to_string());
+    assert_eq!(resp.public_key, public_key);
+
+    // Not registered owner has no public key
+    assert!(query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::PublicKey {
+            owner: RECIPIENT.to_string(),
+        },
+    )
+    .is_err());
+}
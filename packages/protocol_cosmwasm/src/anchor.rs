@@ -32,6 +32,9 @@ pub enum ExecuteMsg {
         commitment: Option<[u8; 32]>,
         amount: Uint128,
     },
+    /// Wraps the native token of `commitments.len() * deposit_size`
+    /// & deposit it into the contract
+    BatchWrapAndDeposit { commitments: Vec<[u8; 32]> },
 
     /// Withdraws the deposit & unwraps into valid token for `sender`
     WithdrawAndUnwrap(WithdrawMsg),
@@ -59,6 +62,9 @@ pub enum Cw20HookMsg {
     /// Depcosit Cw20 tokens
     DepositCw20 { commitment: Option<[u8; 32]> },
 
+    /// Deposit Cw20 tokens of `commitments.len() * deposit_size`
+    BatchDepositCw20 { commitments: Vec<[u8; 32]> },
+
     /// Wraps a cw20 token for the `sender` using
     /// the underlying Anchor's TokenWrapper contract
     WrapToken {},
@@ -69,6 +75,9 @@ pub enum Cw20HookMsg {
         commitment: Option<[u8; 32]>,
         amount: Uint128,
     },
+    /// Wraps a cw20 token of `commitments.len() * deposit_size` for the `sender`
+    /// & deposit it into the contract.
+    BatchWrapAndDeposit { commitments: Vec<[u8; 32]> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -88,10 +97,48 @@ pub struct WithdrawMsg {
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Config {},
-    EdgeInfo { id: u64 },
-    NeighborRootInfo { chain_id: u64, id: u32 },
+    EdgeInfo {
+        id: u64,
+    },
+    Edges {},
+    NeighborRootInfo {
+        chain_id: u64,
+        id: u32,
+    },
     MerkleTreeInfo {},
-    MerkleRootInfo { id: u32 },
+    MerkleRootInfo {
+        id: u32,
+    },
+    /// Leaves of the tree of `tree_id`(current tree if not given)
+    Leaves {
+        tree_id: Option<u32>,
+        start_index: Option<u32>,
+        limit: Option<u32>,
+    },
+    LeafIndex {
+        commitment: [u8; 32],
+    },
+    MerklePath {
+        tree_id: Option<u32>,
+        leaf_index: u32,
+    },
+    IsKnownNullifier {
+        nullifier: [u8; 32],
+    },
+    SpentNullifiers {
+        nullifiers: Vec<[u8; 32]>,
+    },
+    IsKnownRoot {
+        root: [u8; 32],
+    },
+    IsKnownNeighborRoot {
+        chain_id: u64,
+        root: [u8; 32],
+    },
+    RootHistory {},
+    NeighborRootHistory {
+        chain_id: u64,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
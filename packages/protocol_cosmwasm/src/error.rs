@@ -43,6 +43,14 @@ pub enum ContractError {
     #[error("Mapping item not found")]
     ItemNotFound,
 
+    /// Returned if the commitment is already inserted into the tree
+    #[error("Commitment already exists")]
+    DuplicateCommitment,
+
+    /// Returned if the leaf was inserted before the merkle nodes were tracked
+    #[error("Merkle path is not available for the leaf inserted before the migration")]
+    MerklePathUnavailable,
+
     /*  ------ Anchor errors ------ */
     #[error("Invalid merkle roots")]
     InvaidMerkleRoots,
@@ -96,10 +104,36 @@ pub enum ContractError {
     #[error("Invalid transaction proof")]
     InvalidTxProof,
 
+    #[error("Invalid insertion batch")]
+    InvalidInsertionBatch,
+
+    #[error("Invalid public key")]
+    InvalidPublicKey,
+
+    /*  ------ PoolFactory errors ------ */
+    #[error("Pool already exists")]
+    PoolAlreadyExists,
+
+    #[error("Invalid pool asset")]
+    InvalidPoolAsset,
+
+    #[error("Pool not found")]
+    PoolNotFound,
+
     /*  ------ TokenWrapper errors ------ */
     // For simplicity, it just converts all the cw20_base errors to Std error.
     #[error("Invalid CW20 token address")]
     InvalidCw20Token,
+
+    #[error("Insufficient reserve of the underlying asset")]
+    InsufficientReserve,
+
+    #[error("Max share of the underlying asset in the reserves exceeded")]
+    MaxShareExceeded,
+
+    /*  ------ Handler errors ------ */
+    #[error("Invalid proposal")]
+    InvalidProposal,
 }
 
 impl From<cw20_base::ContractError> for ContractError {
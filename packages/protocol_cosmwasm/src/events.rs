@@ -0,0 +1,95 @@
+use cosmwasm_std::{attr, Event};
+
+/// Hex-encodes the bytes with "0x" prefix, which is what the indexers expect.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Event emitted for every leaf(commitment) inserted into the pool's merkle tree.
+///
+/// Event type is "<pool>-commitment", eg: "mixer-commitment", "vanchor-commitment".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitmentEvent {
+    pool: String,
+    tree_id: u32,
+    leaf_index: u32,
+    commitment: [u8; 32],
+    root: [u8; 32],
+    encrypted_output: Option<Vec<u8>>,
+}
+
+impl CommitmentEvent {
+    /// `root` is the merkle root right after the insertion of `commitment`.
+    pub fn new(pool: &str, commitment: [u8; 32], leaf_index: u32, root: [u8; 32]) -> Self {
+        Self {
+            pool: pool.to_string(),
+            tree_id: 0,
+            leaf_index,
+            commitment,
+            root,
+            encrypted_output: None,
+        }
+    }
+
+    pub fn tree_id(mut self, tree_id: u32) -> Self {
+        self.tree_id = tree_id;
+        self
+    }
+
+    pub fn encrypted_output(mut self, encrypted_output: &[u8]) -> Self {
+        self.encrypted_output = Some(encrypted_output.to_vec());
+        self
+    }
+
+    pub fn build(self) -> Event {
+        let mut event = Event::new(format!("{}-commitment", self.pool)).add_attributes(vec![
+            attr("tree_id", self.tree_id.to_string()),
+            attr("leaf_index", self.leaf_index.to_string()),
+            attr("commitment", hex_encode(&self.commitment)),
+            attr("root", hex_encode(&self.root)),
+        ]);
+        if let Some(encrypted_output) = self.encrypted_output {
+            event = event.add_attribute("encrypted_output", hex_encode(&encrypted_output));
+        }
+        event
+    }
+}
+
+impl From<CommitmentEvent> for Event {
+    fn from(event: CommitmentEvent) -> Self {
+        event.build()
+    }
+}
+
+/// Event emitted for every nullifier spent in the pool.
+///
+/// Event type is "<pool>-nullifier", eg: "mixer-nullifier", "vanchor-nullifier".
+///
+/// Unlike `CommitmentEvent`, it has no `tree_id`, since the nullifiers are global across the trees:
+/// they are stored in a single set per pool, and the proof may be against the root of any tree
+/// in the root history, so the spent tree is not known to the pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NullifierEvent {
+    pool: String,
+    nullifier: [u8; 32],
+}
+
+impl NullifierEvent {
+    pub fn new(pool: &str, nullifier: [u8; 32]) -> Self {
+        Self {
+            pool: pool.to_string(),
+            nullifier,
+        }
+    }
+
+    pub fn build(self) -> Event {
+        Event::new(format!("{}-nullifier", self.pool))
+            .add_attribute("nullifier", hex_encode(&self.nullifier))
+    }
+}
+
+impl From<NullifierEvent> for Event {
+    fn from(event: NullifierEvent) -> Self {
+        event.build()
+    }
+}
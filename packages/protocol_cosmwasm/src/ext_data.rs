@@ -0,0 +1,167 @@
+use ark_bn254::Fr as Bn254;
+use ark_ff::{BigInteger, PrimeField};
+use arkworks_setups::common::keccak_256;
+use cosmwasm_std::Api;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::keccak::Keccak256;
+use crate::utils::element_encoder;
+use crate::vanchor::ExtData;
+
+/// Layout of the "ext_data", which is hashed into "ext_data_hash" of the proof.
+///    "Cosmos"   recipient & relayer strings sliced into 32 bytes, little-endian amounts,
+///               raw encrypted outputs,
+///               "keccak256" hash reduced into the field as little-endian value.
+///    "Abi"      Solidity `abi.encode(extData)` of the `ExtData` struct
+///               `(recipient, extAmount, relayer, fee, encryptedOutput1, encryptedOutput2)`
+///               with canonical addresses, same as the EVM VAnchor.
+///               "keccak256" hash reduced into the field as big-endian value(`uint256(hash) % FIELD_SIZE`).
+/// In both cases, the result is the little-endian bytes of the field element.
+/// If the `hook` is given, it is bound into the hash as the last value(`bytes hook` member in "Abi" layout).
+/// In "Cosmos" layout, the `hook` is appended as the tag word(1) & length word(little-endian), followed by its bytes,
+/// so that `Some(empty)` differs from `None`. Without the `hook`, the layout is unchanged.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtDataEncoding {
+    Cosmos,
+    Abi,
+}
+
+impl Default for ExtDataEncoding {
+    fn default() -> Self {
+        ExtDataEncoding::Cosmos
+    }
+}
+
+/// Computes the "ext_data_hash" of `ext_data` with the given `encoding`
+pub fn hash_ext_data(
+    api: &dyn Api,
+    ext_data: &ExtData,
+    encoding: ExtDataEncoding,
+) -> Result<[u8; 32], ContractError> {
+    match encoding {
+        ExtDataEncoding::Cosmos => hash_cosmos_ext_data(ext_data),
+        ExtDataEncoding::Abi => {
+            let recipient = api.addr_canonicalize(&ext_data.recipient)?;
+            let relayer = api.addr_canonicalize(&ext_data.relayer)?;
+            hash_abi_ext_data(ext_data, recipient.as_slice(), relayer.as_slice())
+        }
+    }
+}
+
+/// Computes the "ext_data_hash" of `ext_data` in "Cosmos" layout
+pub fn hash_cosmos_ext_data(ext_data: &ExtData) -> Result<[u8; 32], ContractError> {
+    let mut ext_data_args = Vec::new();
+    ext_data_args.extend_from_slice(&element_encoder(ext_data.recipient.as_bytes()));
+    ext_data_args.extend_from_slice(&element_encoder(ext_data.relayer.as_bytes()));
+    ext_data_args.extend_from_slice(&element_encoder(&ext_data.ext_amount.to_le_bytes()));
+    ext_data_args.extend_from_slice(&element_encoder(&ext_data.fee.u128().to_le_bytes()));
+    ext_data_args.extend_from_slice(&ext_data.encrypted_output1);
+    ext_data_args.extend_from_slice(&ext_data.encrypted_output2);
+    if let Some(hook) = &ext_data.hook {
+        ext_data_args.extend_from_slice(&element_encoder(&[1]));
+        ext_data_args.extend_from_slice(&element_encoder(&(hook.len() as u64).to_le_bytes()));
+        ext_data_args.extend_from_slice(hook.as_slice());
+    }
+
+    Keccak256::hash(&ext_data_args).map_err(|_| ContractError::HashError)
+}
+
+/// Computes the "ext_data_hash" of `ext_data` in "Abi" layout,
+/// with the canonical bytes of `recipient` & `relayer` addresses.
+pub fn hash_abi_ext_data(
+    ext_data: &ExtData,
+    recipient: &[u8],
+    relayer: &[u8],
+) -> Result<[u8; 32], ContractError> {
+    let encoded = abi_encode_ext_data(ext_data, recipient, relayer)?;
+    let hash = keccak_256(&encoded);
+    let field_res = Bn254::from_be_bytes_mod_order(&hash);
+    field_res
+        .into_repr()
+        .to_bytes_le()
+        .try_into()
+        .map_err(|_| ContractError::HashError)
+}
+
+/// Encodes `ext_data` same as Solidity `abi.encode(extData)` of the struct
+/// `ExtData(address recipient, int256 extAmount, address relayer, uint256 fee, bytes encryptedOutput1, bytes encryptedOutput2)`
+/// with the last `bytes hook` member, if the `hook` is given.
+/// Since the struct has dynamic members, it is encoded as the offset of the tuple(0x20),
+/// followed by the tuple itself.
+pub fn abi_encode_ext_data(
+    ext_data: &ExtData,
+    recipient: &[u8],
+    relayer: &[u8],
+) -> Result<Vec<u8>, ContractError> {
+    let mut dynamic_values = vec![
+        ext_data.encrypted_output1.as_slice(),
+        ext_data.encrypted_output2.as_slice(),
+    ];
+    if let Some(hook) = &ext_data.hook {
+        dynamic_values.push(hook.as_slice());
+    }
+
+    // Offset of the tuple
+    let mut encoded = abi_uint256(32).to_vec();
+
+    // Static part: 4 words of values & the offsets of "bytes" values(relative to the tuple)
+    encoded.extend_from_slice(&abi_address(recipient)?);
+    encoded.extend_from_slice(&abi_int256(ext_data.ext_amount.i128()));
+    encoded.extend_from_slice(&abi_address(relayer)?);
+    encoded.extend_from_slice(&abi_uint256(ext_data.fee.u128()));
+
+    let mut offset = (4 + dynamic_values.len()) * 32;
+    for value in dynamic_values.iter() {
+        encoded.extend_from_slice(&abi_uint256(offset as u128));
+        offset += 32 + padded_len(value.len());
+    }
+
+    // Dynamic part
+    for value in dynamic_values {
+        encoded.extend_from_slice(&abi_bytes(value));
+    }
+    Ok(encoded)
+}
+
+// Address bytes, left-padded into 32 bytes word
+fn abi_address(address: &[u8]) -> Result<[u8; 32], ContractError> {
+    if address.len() > 32 {
+        return Err(ContractError::InvalidExtData);
+    }
+    let mut word = [0u8; 32];
+    word[32 - address.len()..].copy_from_slice(address);
+    Ok(word)
+}
+
+// Big-endian, sign-extended into 32 bytes word
+fn abi_int256(value: i128) -> [u8; 32] {
+    let mut word = if value.is_negative() {
+        [0xffu8; 32]
+    } else {
+        [0u8; 32]
+    };
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+// Big-endian, left-padded into 32 bytes word
+fn abi_uint256(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+// Length word, followed by the data right-padded to the multiple of 32 bytes
+fn abi_bytes(data: &[u8]) -> Vec<u8> {
+    let mut encoded = abi_uint256(data.len() as u128).to_vec();
+    encoded.extend_from_slice(data);
+    encoded.resize(32 + padded_len(data.len()), 0);
+    encoded
+}
+
+fn padded_len(len: usize) -> usize {
+    (len + 31) / 32 * 32
+}
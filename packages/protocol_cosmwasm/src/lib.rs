@@ -2,12 +2,17 @@ pub mod anchor;
 pub mod anchor_handler;
 pub mod anchor_verifier;
 pub mod error;
+pub mod events;
 pub mod executor;
+pub mod ext_data;
 pub mod field_ops;
 pub mod keccak;
 pub mod linkable_anchor;
+pub mod merkle_tree;
 pub mod mixer;
 pub mod mixer_verifier;
+pub mod pool_factory;
+pub mod pool_factory_handler;
 pub mod poseidon;
 pub mod signature_bridge;
 pub mod structs;
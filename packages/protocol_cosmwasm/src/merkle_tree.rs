@@ -0,0 +1,99 @@
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+
+use crate::error::ContractError;
+use crate::zeroes;
+
+/// MerkleTree nodes ((tree_id, level, index) -> hash)
+/// The level 0 holds the leaves and the level `levels` holds the root.
+/// The nodes on the right of the last inserted leaf are not stored,
+/// since they are the zero(empty) subtrees.
+pub const MERKLE_NODES: Map<(String, String, String), [u8; 32]> = Map::new("merkle_nodes");
+
+/// The first (tree_id, leaf_index) whose merkle path can be computed from the `MERKLE_NODES`.
+/// Only saved on the migration of the pools deployed before the node tracking.
+pub const MERKLE_NODES_START: Item<(u32, u32)> = Item::new("merkle_nodes_start");
+
+pub fn save_node(
+    store: &mut dyn Storage,
+    tree_id: u32,
+    level: u32,
+    index: u32,
+    data: &[u8; 32],
+) -> StdResult<()> {
+    MERKLE_NODES.save(
+        store,
+        (tree_id.to_string(), level.to_string(), index.to_string()),
+        data,
+    )
+}
+
+pub fn read_node(
+    store: &dyn Storage,
+    tree_id: u32,
+    level: u32,
+    index: u32,
+) -> StdResult<Option<[u8; 32]>> {
+    MERKLE_NODES.may_load(
+        store,
+        (tree_id.to_string(), level.to_string(), index.to_string()),
+    )
+}
+
+/// Computes the merkle path of the leaf at `leaf_index` in the tree of `tree_id`,
+/// reading a single sibling node per level.
+/// Returns the sibling hashes, path bits(`true` if the node is the right child) & root.
+pub fn merkle_path(
+    store: &dyn Storage,
+    tree_id: u32,
+    levels: u32,
+    leaf_index: u32,
+) -> Result<(Vec<[u8; 32]>, Vec<bool>, [u8; 32]), ContractError> {
+    if let Some(start) = MERKLE_NODES_START.may_load(store)? {
+        if (tree_id, leaf_index) < start {
+            return Err(ContractError::MerklePathUnavailable);
+        }
+    }
+
+    let mut current_index = leaf_index;
+    let mut siblings: Vec<[u8; 32]> = vec![];
+    let mut path_bits: Vec<bool> = vec![];
+
+    for i in 0..levels {
+        let sibling = read_node(store, tree_id, i, current_index ^ 1)?;
+        siblings.push(sibling.unwrap_or_else(|| zeroes::zeroes(i)));
+        path_bits.push(current_index % 2 == 1);
+        current_index /= 2;
+    }
+
+    let root = read_node(store, tree_id, levels, 0)?.ok_or(ContractError::ItemNotFound)?;
+    Ok((siblings, path_bits, root))
+}
+
+/// Starts the node tracking of the tree deployed before it, on migration.
+/// The nodes of the leaves inserted so far are unknown, so only the left siblings of the next leaf path
+/// (the "filled subtrees") & the root are saved, which is enough for the merkle paths of the next leaves.
+/// The earlier leaves are rejected by `merkle_path` from now on.
+/// Does nothing if the tree is empty or already tracked.
+pub fn start_node_tracking(
+    store: &mut dyn Storage,
+    tree_id: u32,
+    levels: u32,
+    next_index: u32,
+    root: [u8; 32],
+    read_subtree: fn(&dyn Storage, u32) -> StdResult<[u8; 32]>,
+) -> StdResult<()> {
+    if (tree_id == 0 && next_index == 0) || read_node(store, tree_id, levels, 0)?.is_some() {
+        return Ok(());
+    }
+
+    for i in 0..levels {
+        let current_index = next_index >> i;
+        if current_index % 2 == 1 {
+            let left = read_subtree(store, i)?;
+            save_node(store, tree_id, i, current_index - 1, &left)?;
+        }
+    }
+    save_node(store, tree_id, levels, 0, &root)?;
+    MERKLE_NODES_START.save(store, &(tree_id, next_index))
+}
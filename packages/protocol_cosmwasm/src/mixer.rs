@@ -15,6 +15,7 @@ pub struct InstantiateMsg {
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Deposit(DepositMsg),
+    BatchDeposit(BatchDepositMsg),
     Withdraw(WithdrawMsg),
     Receive(Cw20ReceiveMsg),
 }
@@ -24,11 +25,19 @@ pub struct DepositMsg {
     pub commitment: Option<[u8; 32]>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct BatchDepositMsg {
+    pub commitments: Vec<[u8; 32]>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
     /// Depcosit Cw20 tokens
     DepositCw20 { commitment: Option<[u8; 32]> },
+
+    /// Deposit Cw20 tokens of `commitments.len() * deposit_size`
+    BatchDepositCw20 { commitments: Vec<[u8; 32]> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -48,7 +57,32 @@ pub struct WithdrawMsg {
 pub enum QueryMsg {
     Config {},
     MerkleTreeInfo {},
-    MerkleRoot { id: u32 },
+    MerkleRoot {
+        id: u32,
+    },
+    /// Leaves of the tree of `tree_id`(current tree if not given)
+    Leaves {
+        tree_id: Option<u32>,
+        start_index: Option<u32>,
+        limit: Option<u32>,
+    },
+    LeafIndex {
+        commitment: [u8; 32],
+    },
+    MerklePath {
+        tree_id: Option<u32>,
+        leaf_index: u32,
+    },
+    IsKnownNullifier {
+        nullifier: [u8; 32],
+    },
+    SpentNullifiers {
+        nullifiers: Vec<[u8; 32]>,
+    },
+    IsKnownRoot {
+        root: [u8; 32],
+    },
+    RootHistory {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -63,9 +97,13 @@ pub struct MerkleTreeInfoResponse {
     pub levels: u32,
     pub current_root_index: u32,
     pub next_index: u32,
+    pub tree_id: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct MerkleRootResponse {
     pub root: [u8; 32],
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MigrateMsg {}
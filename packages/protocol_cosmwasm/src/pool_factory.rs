@@ -0,0 +1,122 @@
+use cosmwasm_std::{Binary, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::anchor::InstantiateMsg as AnchorInstantiateMsg;
+use crate::mixer::InstantiateMsg as MixerInstantiateMsg;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address of handler, which executes the pool creation proposals
+    /// Usually the "pool-factory-handler", which parses & checks the bridge proposal header.
+    pub handler: String,
+    /// Code id of "mixer" contract
+    pub mixer_code_id: u64,
+    /// Code id of "anchor" contract
+    pub anchor_code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Instantiates a new pool for the (asset, denomination) & registers it
+    /// Only the "handler" can execute it, with the "nonce" greater than the last one.
+    CreatePool { pool: PoolInitMsg, nonce: u32 },
+
+    /// Sets a new handler for contract
+    SetHandler { handler: String, nonce: u32 },
+
+    /// Migrates the registered `pool`(instantiated with the factory as admin) to `new_code_id`
+    /// Only the "handler" can execute it, with the "nonce" greater than the last one.
+    MigratePool {
+        pool: String,
+        new_code_id: u64,
+        msg: Binary,
+        nonce: u32,
+    },
+
+    /// Hands over the admin of the registered `pool` to the `admin`(eg: governance account)
+    /// Only the "handler" can execute it, with the "nonce" greater than the last one.
+    UpdatePoolAdmin {
+        pool: String,
+        admin: String,
+        nonce: u32,
+    },
+}
+
+impl ExecuteMsg {
+    /// Returns the `nonce` of the proposal, which the "pool-factory-handler"
+    /// matches against the one of proposal header.
+    pub fn proposal_nonce(&self) -> u32 {
+        match self {
+            ExecuteMsg::CreatePool { nonce, .. }
+            | ExecuteMsg::SetHandler { nonce, .. }
+            | ExecuteMsg::MigratePool { nonce, .. }
+            | ExecuteMsg::UpdatePoolAdmin { nonce, .. } => *nonce,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolInitMsg {
+    Mixer(MixerInstantiateMsg),
+    Anchor(AnchorInstantiateMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolKind {
+    Mixer,
+    Anchor,
+}
+
+impl PoolKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PoolKind::Mixer => "mixer",
+            PoolKind::Anchor => "anchor",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Get the config
+    Config {},
+    /// Get the pool of given (kind, asset, denomination)
+    /// "asset" is native token denom or cw20 token address
+    /// (always "TokenWrapper" address for "anchor")
+    Pool {
+        kind: PoolKind,
+        asset: String,
+        deposit_size: Uint128,
+    },
+    /// List all the registered pools, ordered by pool address
+    Pools {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ConfigResponse {
+    pub handler: String,
+    pub proposal_nonce: u32,
+    pub mixer_code_id: u64,
+    pub anchor_code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PoolResponse {
+    pub kind: PoolKind,
+    pub asset: String,
+    pub deposit_size: Uint128,
+    pub address: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PoolsResponse {
+    pub pools: Vec<PoolResponse>,
+}
@@ -0,0 +1,48 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// @dev {initial_resource_ids} and {initial_contract_addresses} must have the same length (one resourceID for every address).
+// Also, these arrays must be ordered in the way that {initial_resource_ids}[0] is the intended resourceID for {initial_contract_addresses}[0].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InstantiateMsg {
+    // Contract address of previously deployed Bridge.
+    pub bridge_addr: String,
+    // Resource IDs are used to identify a specific contract address.
+    // These are the Resource IDs this contract will initially support.
+    pub initial_resource_ids: Vec<[u8; 32]>,
+    // These are the addresses(pool factories) the {initial_resource_ids} will point to.
+    pub initial_contract_addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /* ---  Handler common utils --- */
+    SetResource {
+        resource_id: [u8; 32],
+        contract_addr: String,
+    },
+
+    MigrateBridge {
+        new_bridge: String,
+    },
+
+    /* ---  PoolFactory-handler specific execution entries --- */
+    // Proposal execution should be initiated when a proposal is finalized in the Bridge contract.
+    // The `data` is the proposal header(resource id, function signature, nonce),
+    // followed by the JSON-encoded pool factory `ExecuteMsg`.
+    ExecuteProposal {
+        resource_id: [u8; 32],
+        data: Vec<u8>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /* ---  Handler common queries --- */
+    GetBridgeAddress {},
+    GetContractAddress { resource_id: [u8; 32] },
+    GetResourceId { contract_addr: String },
+    IsContractWhitelisted { contract_addr: String },
+}
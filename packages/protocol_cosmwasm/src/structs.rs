@@ -10,6 +10,10 @@ pub const COSMOS_CHAIN_TYPE: [u8; 2] = [4, 0]; // 0x0400
 // History length for the "curr_neighbor_root_index".
 pub const HISTORY_LENGTH: u32 = 30;
 
+// Pagination limits for the "leaves" query
+pub const DEFAULT_LEAVES_LIMIT: u32 = 100;
+pub const MAX_LEAVES_LIMIT: u32 = 1000;
+
 pub type ChainId = u64;
 pub type Element = [u8; 32];
 pub type LatestLeafIndex = u32;
@@ -36,6 +40,22 @@ pub struct EdgeInfoResponse {
     pub target: [u8; 32],
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct EdgeState {
+    pub src_chain_id: u64,
+    pub root: [u8; 32],
+    pub latest_leaf_index: u32,
+    pub target: [u8; 32],
+    /// index of the latest root in the neighbor root history ring
+    pub curr_neighbor_root_index: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct EdgesResponse {
+    /// edges in the same order as the neighbor `roots` expected by the pool
+    pub edges: Vec<EdgeState>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct NeighborRootInfoResponse {
     pub neighbor_root: [u8; 32],
@@ -46,6 +66,7 @@ pub struct MerkleTreeInfoResponse {
     pub levels: u32,
     pub curr_root_index: u32,
     pub next_index: u32,
+    pub tree_id: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -53,6 +74,53 @@ pub struct MerkleRootInfoResponse {
     pub root: [u8; 32],
 }
 
+/* ------     Mixer/Anchor/Vanchor-common responses  -------- */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LeavesResponse {
+    pub tree_id: u32,
+    pub start_index: u32,
+    pub leaves: Vec<[u8; 32]>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LeafIndexResponse {
+    pub tree_id: u32,
+    pub leaf_index: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MerklePathResponse {
+    pub tree_id: u32,
+    pub leaf_index: u32,
+    pub root: [u8; 32],
+    /// sibling hashes from the leaf level up to the root
+    pub siblings: Vec<[u8; 32]>,
+    /// `true` if the node on the path is the right child
+    pub path_bits: Vec<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct IsKnownNullifierResponse {
+    pub known: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SpentNullifiersResponse {
+    /// spent status of the queried nullifiers, in the same order
+    pub spent: Vec<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct IsKnownRootResponse {
+    pub known: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct RootHistoryResponse {
+    /// valid roots, from the latest to the oldest
+    pub roots: Vec<[u8; 32]>,
+}
+
 /* ------    Handler-common responses     ------ */
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct BridgeAddrResponse {
@@ -14,13 +14,15 @@ pub struct InstantiateMsg {
     pub decimals: u8,
 
     /* --- Governance - related params --- */
-    /// addr of governor
+    /// addr of (emergency) governor
     pub governor: Option<String>,
+    /// addr of handler, which executes the proposals(governor if not given)
+    pub handler: Option<String>,
     /// addr of fee recipient
     pub fee_recipient: String,
     /// fee_percentage( 0 ~ 10,000 )
     pub fee_percentage: u16,
-    /// native token denom string to be wrapped
+    /// default native token denom string to be wrapped
     pub native_token_denom: String,
     /// flag of is_native_allowed
     pub is_native_allowed: bool,
@@ -32,16 +34,31 @@ pub struct InstantiateMsg {
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /* ---    TokenWrapper functionality  ---- */
-    /// Wrap the native token for "sender" address/tx sender address.
+    /// Wrap the native tokens(any of the allowed denoms) for "sender" address/tx sender address.
     Wrap {
         sender: Option<String>,
         recipient: Option<String>,
     },
 
+    /// Wrap the native tokens(any of the allowed denoms) for tx sender address
+    /// & send the wrapped tokens to the `contract` with the `msg`(same as cw20 "Send").
+    WrapAndCall { contract: String, msg: Binary },
+
     /// Unwrap the underlying tokens & re-send the fund(native or Cw20)
+    /// The native token of `denom`(default native token denom if not given) is sent
+    /// if `token` is not given.
     Unwrap {
         sender: Option<String>,
         token: Option<Addr>,
+        denom: Option<String>,
+        amount: Uint128,
+        recipient: Option<String>,
+    },
+
+    /// Burns the `amount` of wrapped token & sends every underlying asset(native and cw20)
+    /// proportionally to the reserves. The rounding dust is retained in the reserves.
+    /// The unwrap fee is charged on every payout, same as `Unwrap`.
+    UnwrapProRata {
         amount: Uint128,
         recipient: Option<String>,
     },
@@ -52,27 +69,88 @@ pub enum ExecuteMsg {
     /* ----------------------------------- */
 
     /* ---  Governance functionality  --- */
-    /// Update the `governor`
+    /// Update the `governor`. Only the governor can execute this entry.
     ConfigureGovernor { governor: Option<String> },
 
+    /// Sets a new handler for contract.
+    /// The governor can also execute this entry to replace the handler in emergency.
+    SetHandler { handler: String, nonce: u64 },
+
+    /* Below entries are the proposals, which only the handler can execute with the `nonce` */
     /// Update the `is_native_allowed`
-    ConfigureNativeAllowed { is_native_allowed: Option<bool> },
+    ConfigureNativeAllowed {
+        is_native_allowed: Option<bool>,
+        nonce: u64,
+    },
 
     /// Update the `wrapping_limit`
-    ConfigureWrappingLimit { wrapping_limit: Option<Uint128> },
+    ConfigureWrappingLimit {
+        wrapping_limit: Option<Uint128>,
+        nonce: u64,
+    },
 
     /// Update the `fee_recipient`
-    ConfigureFeeRecipient { fee_recipient: Option<String> },
+    ConfigureFeeRecipient {
+        fee_recipient: Option<String>,
+        nonce: u64,
+    },
 
     /// Update the `fee_percentage`
-    ConfigureFeePercentage { fee_percentage: Option<u16> },
+    ConfigureFeePercentage {
+        fee_percentage: Option<u16>,
+        nonce: u64,
+    },
 
-    /// Add cw20 token address to wrapping list
+    /// Add cw20 token address to wrapping list, recording its decimals.
+    /// The wrapped amounts are scaled from/to the token decimals on wrapping/unwrapping.
     AddCw20TokenAddr { token: String, nonce: u64 },
 
     /// Remove cw20 token address from wrapping list (disallow wrapping)
     RemoveCw20TokenAddr { token: String, nonce: u64 },
 
+    /// Add native token denom(eg: IBC denom) with its `decimals` to wrapping list
+    AddNativeTokenDenom {
+        denom: String,
+        decimals: u8,
+        nonce: u64,
+    },
+
+    /// Remove native token denom from wrapping list (disallow wrapping)
+    RemoveNativeTokenDenom { denom: String, nonce: u64 },
+
+    /// Update the `unwrap_fee_percentage`( 0 ~ 10,000 ) charged on unwrapping.
+    /// The unwrap fee is sent to `fee_recipient`, or retained in the reserves if `is_unwrap_fee_retained`.
+    ConfigureUnwrapFee {
+        unwrap_fee_percentage: u16,
+        is_unwrap_fee_retained: bool,
+        nonce: u64,
+    },
+
+    /// Add/remove the `caller`(eg: anchor, vanchor) to/from the trusted callers,
+    /// which can wrap/unwrap on behalf of any account without the allowance
+    ConfigureTrustedCaller {
+        caller: String,
+        is_trusted: bool,
+        nonce: u64,
+    },
+
+    /// Set the `fee_percentage`( 0 ~ 10,000 ) of the `token`(default native token denom if not given).
+    /// The `fee_percentage` of config is applied to the `token` if `None`.
+    /// Here & below, `token` is either the cw20 token address or the native token denom.
+    ConfigureTokenFeePercentage {
+        token: Option<String>,
+        fee_percentage: Option<u16>,
+        nonce: u64,
+    },
+
+    /// Set the max share( 0 ~ 10,000 ) of the `token`(default native token denom if not given) in the reserves.
+    /// The `token` is not limited if `None`.
+    ConfigureTokenWrappingLimit {
+        token: Option<String>,
+        max_share: Option<u16>,
+        nonce: u64,
+    },
+
     /* ---------------------------------- */
 
     /* ---      Cw20 functions       --- */
@@ -122,6 +200,28 @@ pub enum ExecuteMsg {
     BurnFrom { owner: String, amount: Uint128 },
 }
 
+impl ExecuteMsg {
+    /// Returns the `nonce` if the message is a proposal executed by the handler
+    pub fn proposal_nonce(&self) -> Option<u64> {
+        match self {
+            ExecuteMsg::SetHandler { nonce, .. }
+            | ExecuteMsg::ConfigureNativeAllowed { nonce, .. }
+            | ExecuteMsg::ConfigureWrappingLimit { nonce, .. }
+            | ExecuteMsg::ConfigureFeeRecipient { nonce, .. }
+            | ExecuteMsg::ConfigureFeePercentage { nonce, .. }
+            | ExecuteMsg::AddCw20TokenAddr { nonce, .. }
+            | ExecuteMsg::RemoveCw20TokenAddr { nonce, .. }
+            | ExecuteMsg::AddNativeTokenDenom { nonce, .. }
+            | ExecuteMsg::RemoveNativeTokenDenom { nonce, .. }
+            | ExecuteMsg::ConfigureUnwrapFee { nonce, .. }
+            | ExecuteMsg::ConfigureTrustedCaller { nonce, .. }
+            | ExecuteMsg::ConfigureTokenFeePercentage { nonce, .. }
+            | ExecuteMsg::ConfigureTokenWrappingLimit { nonce, .. } => Some(*nonce),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
@@ -132,14 +232,67 @@ pub enum QueryMsg {
     /// Implements CW20 "allowance" extension.
     /// Returns how much spender can use from owner account, 0 if unset.
     Allowance { owner: String, spender: String },
+    /// Implements CW20 "enumerable" extension.
+    /// Returns all allowances this owner has approved. Supports pagination.
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Implements CW20 "enumerable" extension.
+    /// Returns all accounts that have balances. Supports pagination.
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     /// Custom queries
     /// Returns the Config of contract
     Config {},
-    /// Calculates the "fee" from "amount_to_wrap"
-    FeeFromAmount { amount_to_wrap: String },
-    /// Calculates the "amount_to_wrap" for target amt
-    GetAmountToWrap { target_amount: String },
+    /// Calculates the "fee" from "amount_to_wrap" of the `token`(default native token denom if not given)
+    FeeFromAmount {
+        amount_to_wrap: String,
+        token: Option<String>,
+    },
+    /// Calculates the "amount_to_wrap" for target amt of the `token`(default native token denom if not given)
+    GetAmountToWrap {
+        target_amount: String,
+        token: Option<String>,
+    },
+    /// Calculates the unwrap "fee" & "refund" of the `token`(default native token denom if not given)
+    /// from "amount_to_unwrap" of the wrapped token
+    FeeFromUnwrapAmount {
+        amount_to_unwrap: String,
+        token: Option<String>,
+    },
+    /// Returns the decimals, wrapping fee & limit of the `token`(default native token denom if not given)
+    TokenConfig { token: Option<String> },
+    /// Returns the trusted callers
+    TrustedCallers {},
+    /// Returns the reserves of underlying assets(native token & cw20 tokens)
+    Reserves {},
+    /// Returns the cw20 token addresses, which are currently allowed to wrap. Supports pagination.
+    Tokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the cw20 token addresses ever added to wrapping list, which are still unwrappable.
+    /// Supports pagination.
+    HistoricalTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the native token denoms, which are currently allowed to wrap. Supports pagination.
+    NativeDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the native token denoms ever added to wrapping list, which are still unwrappable.
+    /// Supports pagination.
+    HistoricalNativeDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -150,18 +303,24 @@ pub enum Cw20HookMsg {
         sender: Option<String>,
         recipient: Option<String>,
     },
+    /// Wrap Cw20 tokens for the cw20 sender address
+    /// & send the wrapped tokens to the `contract` with the `msg`(same as cw20 "Send").
+    WrapAndCall { contract: String, msg: Binary },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct ConfigResponse {
     pub governor: String,
+    pub handler: String,
     pub native_token_denom: String,
     pub fee_recipient: String,
     pub fee_percentage: String,
     pub is_native_allowed: String,
     pub wrapping_limit: String,
     pub proposal_nonce: String,
+    pub unwrap_fee_percentage: String,
+    pub is_unwrap_fee_retained: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -171,9 +330,62 @@ pub struct FeeFromAmountResponse {
     pub fee_amt: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FeeFromUnwrapAmountResponse {
+    pub amount_to_unwrap: String,
+    pub fee_amt: String,
+    pub refund_amt: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct GetAmountToWrapResponse {
     pub target_amount: String,
     pub amount_to_wrap: String,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TokenConfigResponse {
+    /// native token denom or cw20 token address
+    pub asset: String,
+    pub decimals: u8,
+    pub fee_percentage: u16,
+    pub max_share: Option<u16>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TrustedCallersResponse {
+    pub callers: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct NativeDenomsResponse {
+    pub denoms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Reserve {
+    /// native token denom or cw20 token address
+    pub asset: String,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ReservesResponse {
+    pub reserves: Vec<Reserve>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -1,5 +1,8 @@
+use cosmwasm_std::{Coin, StdError, Uint128};
 use tiny_keccak::{Hasher, Keccak};
 
+use crate::error::ContractError;
+
 /// Slice the length of the bytes array into 32bytes
 pub fn element_encoder(v: &[u8]) -> [u8; 32] {
     let mut output = [0u8; 32];
@@ -64,3 +67,41 @@ pub fn get_chain_id_type(chain_id_type: &[u8]) -> u64 {
     buf[2..8].copy_from_slice(&chain_id_type);
     u64::from_be_bytes(buf)
 }
+
+/// Returns the amount of `denom` tokens sent for the deposit.
+/// Any other token sent along is rejected, since it could never be withdrawn.
+pub fn sent_native_amount(funds: &[Coin], denom: &str) -> Result<Uint128, ContractError> {
+    if funds.iter().any(|x| x.denom != denom) {
+        return Err(ContractError::UnnecessaryFunds {});
+    }
+    Ok(funds.iter().fold(Uint128::zero(), |acc, x| acc + x.amount))
+}
+
+/// Returns the total amount required for depositing all the "commitments".
+pub fn batch_deposit_size(
+    deposit_size: Uint128,
+    commitments: &[[u8; 32]],
+) -> Result<Uint128, ContractError> {
+    if commitments.is_empty() {
+        return Err(ContractError::Std(StdError::NotFound {
+            kind: "Commitment".to_string(),
+        }));
+    }
+    deposit_size
+        .checked_mul(Uint128::from(commitments.len() as u128))
+        .map_err(|e| ContractError::Std(e.into()))
+}
+
+/// Only exact "deposit_size" is accepted, since any excess could never be withdrawn.
+pub fn validate_deposit_amount(
+    amount: Uint128,
+    deposit_size: Uint128,
+) -> Result<(), ContractError> {
+    if amount < deposit_size {
+        return Err(ContractError::InsufficientFunds {});
+    }
+    if amount > deposit_size {
+        return Err(ContractError::InvalidDepositAmount);
+    }
+    Ok(())
+}
@@ -1,7 +1,14 @@
-use cosmwasm_std::Uint128;
+use std::fmt;
+use std::str::FromStr;
+
+use cosmwasm_std::{Binary, StdError, Uint128};
 use cw20::Cw20ReceiveMsg;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ext_data::ExtDataEncoding;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -13,6 +20,8 @@ pub struct InstantiateMsg {
     pub max_ext_amt: Uint128,
     pub max_fee: Uint128,
     pub handler: String,
+    /// Layout of the "ext_data" hashing, "Cosmos" if not given
+    pub ext_data_encoding: Option<ExtDataEncoding>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -93,12 +102,20 @@ pub enum ExecuteMsg {
     ConfigureMaximumDepositLimit {
         maximum_deposit_amount: Uint128,
     },
+
+    /// Registers the shielded account `public_key`(32 or 64 bytes) of `owner`(tx sender),
+    /// which others use to encrypt the UTXOs sent to the `owner`
+    Register {
+        owner: String,
+        public_key: Vec<u8>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct UpdateConfigMsg {
     pub max_ext_amt: Option<Uint128>,
     pub max_fee: Option<Uint128>,
+    pub ext_data_encoding: Option<ExtDataEncoding>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -121,6 +138,15 @@ pub enum Cw20HookMsg {
         proof_data: ProofData,
         ext_data: ExtData,
     },
+
+    /// Registers the shielded account `public_key`(32 or 64 bytes) of `owner`(tx sender)
+    /// & executes a deposit or combination join/split transaction
+    RegisterAndTransactDeposit {
+        owner: String,
+        public_key: Vec<u8>,
+        proof_data: ProofData,
+        ext_data: ExtData,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -157,20 +183,179 @@ impl ProofData {
 pub struct ExtData {
     pub recipient: String,
     pub relayer: String,
-    pub ext_amount: String, // Still `String` since represents `i128` value
+    pub ext_amount: ExtAmount,
     pub fee: Uint128,
     pub encrypted_output1: Vec<u8>,
     pub encrypted_output2: Vec<u8>,
+    /// Payload to deliver the withdrawn tokens to the `recipient` contract,
+    /// via `Cw20ExecuteMsg::Send`(cw20) or wasm execute(native) instead of the plain transfer
+    #[serde(default)]
+    pub hook: Option<Binary>,
+}
+
+/// Signed amount of the tokens deposited into(positive) or withdrawn from(negative) the pool.
+/// It is serialized as a string(e.g. "-10"), like `Uint128`, since JSON numbers
+/// cannot hold every `i128` value. Malformed strings are rejected on deserialization.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExtAmount(i128);
+
+impl ExtAmount {
+    pub const fn new(value: i128) -> Self {
+        ExtAmount(value)
+    }
+
+    pub const fn zero() -> Self {
+        ExtAmount(0)
+    }
+
+    pub const fn i128(&self) -> i128 {
+        self.0
+    }
+
+    /// Tokens are sent to the pool
+    pub const fn is_deposit(&self) -> bool {
+        self.0.is_positive()
+    }
+
+    /// Tokens are sent out from the pool
+    pub const fn is_withdraw(&self) -> bool {
+        self.0.is_negative()
+    }
+
+    /// Amount of the tokens, regardless of the direction
+    pub fn unsigned_abs(&self) -> Uint128 {
+        Uint128::from(self.0.unsigned_abs())
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 16] {
+        self.0.to_le_bytes()
+    }
+}
+
+impl From<i128> for ExtAmount {
+    fn from(value: i128) -> Self {
+        ExtAmount(value)
+    }
+}
+
+impl From<ExtAmount> for i128 {
+    fn from(value: ExtAmount) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for ExtAmount {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<i128>()
+            .map(ExtAmount)
+            .map_err(|e| StdError::generic_err(format!("Parsing ext_amount: {}", e)))
+    }
+}
+
+impl fmt::Display for ExtAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for ExtAmount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtAmount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ExtAmountVisitor)
+    }
+}
+
+struct ExtAmountVisitor;
+
+impl<'de> de::Visitor<'de> for ExtAmountVisitor {
+    type Value = ExtAmount;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string-encoded signed integer")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse::<i128>()
+            .map(ExtAmount)
+            .map_err(|e| E::custom(format!("invalid ext_amount '{}' - {}", v, e)))
+    }
+}
+
+impl JsonSchema for ExtAmount {
+    fn schema_name() -> String {
+        "ExtAmount".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Config {},
-    EdgeInfo { id: u64 },
-    NeighborRootInfo { chain_id: u64, id: u32 },
+    EdgeInfo {
+        id: u64,
+    },
+    Edges {},
+    NeighborRootInfo {
+        chain_id: u64,
+        id: u32,
+    },
     MerkleTreeInfo {},
-    MerkleRootInfo { id: u32 },
+    MerkleRootInfo {
+        id: u32,
+    },
+    /// Leaves of the tree of `tree_id`(current tree if not given)
+    Leaves {
+        tree_id: Option<u32>,
+        start_index: Option<u32>,
+        limit: Option<u32>,
+    },
+    LeafIndex {
+        commitment: [u8; 32],
+    },
+    MerklePath {
+        tree_id: Option<u32>,
+        leaf_index: u32,
+    },
+    IsKnownNullifier {
+        nullifier: [u8; 32],
+    },
+    SpentNullifiers {
+        nullifiers: Vec<[u8; 32]>,
+    },
+    IsKnownRoot {
+        root: [u8; 32],
+    },
+    IsKnownNeighborRoot {
+        chain_id: u64,
+        root: [u8; 32],
+    },
+    RootHistory {},
+    NeighborRootHistory {
+        chain_id: u64,
+    },
+    PublicKey {
+        owner: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -182,6 +367,13 @@ pub struct ConfigResponse {
     pub min_withdraw_amt: String,
     pub max_ext_amt: String,
     pub max_fee: String,
+    pub ext_data_encoding: ExtDataEncoding,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PublicKeyResponse {
+    pub owner: String,
+    pub public_key: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
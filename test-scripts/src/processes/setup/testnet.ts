@@ -311,6 +311,26 @@ async function setup(
     vanchor = vanchorResult.contractAddress;
     console.log(chalk.green(" Done!"), `${chalk.blue("contractAddress")}=${vanchor}`);     
 
+    // Allow "Anchor" & "VAnchor" to wrap/unwrap on behalf of the users
+    const trustedCallers = [anchor, vanchor];
+    for (let i = 0; i < trustedCallers.length; i++) {
+        await junod.execute(localjuno.addresses.wallet1, tokenWrapper, {
+            configure_trusted_caller: {
+                caller: trustedCallers[i],
+                is_trusted: true,
+                nonce: 2 + i,
+            }
+        }, "auto", undefined, []);
+    }
+
+    // Hand over the "TokenWrapper" governance to "TokenWrapperHandler"
+    await junod.execute(localjuno.addresses.wallet1, tokenWrapper, {
+        set_handler: {
+            handler: tokenWrapperHandler,
+            nonce: 4,
+        }
+    }, "auto", undefined, []);
+
     // Mixer
     process.stdout.write("Instantiating Mixer contract");
     
@@ -33,7 +33,8 @@ export async function testTokenWrapperInitialize(
     expect(result.fee_percentage == localjuno.contractsConsts.feePercentage.toString()).to.be.ok;
     expect(result.is_native_allowed == localjuno.contractsConsts.isNativeAllowed.toString()).to.be.ok;
     expect(result.wrapping_limit == localjuno.contractsConsts.tokenWrapperWrappingLimit).to.be.ok;
-    expect(result.proposal_nonce == "1").to.be.ok;
+    expect(result.handler == localjuno.contracts.tokenWrapperHandler).to.be.ok;
+    expect(result.proposal_nonce == "4").to.be.ok;
     
     console.log(chalk.green(" Passed!"));
   }